@@ -13,3 +13,78 @@ pub const MINT_VAULT_SEED: &[u8] = b"mint_vault";
 
 #[constant]
 pub const DISPUTE_VAULT_SEED: &[u8] = b"dispute_vault";
+
+#[constant]
+pub const SIGNER_SET_SEED: &[u8] = b"signer_set";
+
+#[constant]
+pub const MAX_SIGNER_SET_SIGNERS: u8 = 10;
+
+#[constant]
+pub const PAYMENT_ATTESTER_SEED: &[u8] = b"payment_attester";
+
+#[constant]
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+
+#[constant]
+pub const DISPUTE_EVIDENCE_SEED: &[u8] = b"dispute_evidence";
+
+#[constant]
+pub const MAX_EVIDENCE_URI_LEN: u16 = 200;
+
+// per party, one party cannot use up the other's submissions
+#[constant]
+pub const MAX_EVIDENCE_ENTRIES: u8 = 20;
+
+#[constant]
+pub const ARBITER_REGISTRY_SEED: &[u8] = b"arbiter_registry";
+
+#[constant]
+pub const MAX_ARBITERS: u8 = 32;
+
+#[constant]
+pub const MAX_PANEL_SIZE: u8 = 9;
+
+// slashable arbiters of a dispute, the panel members and the arbiter of the appealed ruling
+pub const MAX_SLASHED_ARBITERS: u8 = MAX_PANEL_SIZE + 1;
+
+// arbiters are drawn with the hash of a slot this far in the future (unknown when scheduled)
+#[constant]
+pub const ARBITER_DRAW_DELAY_SLOTS: u64 = 10;
+
+// initial dispute response window, updatable with `update_global_config`
+pub const DEFAULT_DISPUTE_RESPONSE_SECS: i64 = 172_800; // 2 days
+
+// initial resolution SLA, updatable with `set_resolution_policy`
+pub const DEFAULT_RESOLUTION_SLA_SECS: i64 = 259_200; // 3 days
+
+// time an arbiter must wait between requesting the unstake and withdrawing it
+#[constant]
+pub const ARBITER_UNBONDING_SECS: i64 = 604_800; // 7 days
+
+// longest single extension of the fiat deadline granted by the seller
+#[constant]
+pub const MAX_FIAT_DEADLINE_EXTENSION_SECS: i64 = 604_800; // 7 days
+
+#[constant]
+pub const MERCHANT_PROFILE_SEED: &[u8] = b"merchant_profile";
+
+// time a merchant must wait between requesting the unstake and withdrawing the bond
+#[constant]
+pub const MERCHANT_UNBONDING_SECS: i64 = 604_800; // 7 days
+
+#[constant]
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+
+// mints whose released volume is kept in `UserStats` (the first ones traded)
+#[constant]
+pub const MAX_USER_STATS_MINTS: u8 = 8;
+
+// initial arbiters configuration, updatable with `set_arbiter_config`
+pub const DEFAULT_MIN_ARBITER_STAKE: u64 = 0; // lamports
+pub const DEFAULT_ARBITER_REWARD_BPS: u16 = 5_000; // 50% of the forfeited deposits
+
+// initial panel configuration, updatable with `set_panel_config`
+pub const DEFAULT_PANEL_SIZE: u8 = 3;
+pub const DEFAULT_PANEL_QUORUM: u8 = 2;
+pub const DEFAULT_PANEL_VOTING_SECS: i64 = 259_200; // 3 days
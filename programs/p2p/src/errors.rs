@@ -20,4 +20,124 @@ pub enum P2pError {
     UnauthorizedDispute,
     #[msg("The escrow is already in dispute.")]
     EscrowAlreadyInDispute,
+    #[msg("Invalid signer set.")]
+    InvalidSignerSet,
+    #[msg("Invalid signer index.")]
+    InvalidSignerIndex,
+    #[msg("Duplicate approval from the same signer.")]
+    DuplicateApproval,
+    #[msg("Not enough approvals to release the escrow.")]
+    NotEnoughApprovals,
+    #[msg("The approval does not match the escrow release approver.")]
+    InvalidReleaseApproval,
+    #[msg("Invalid secp256k1 recovery id.")]
+    InvalidRecoveryId,
+    #[msg("Invalid WebAuthn assertion.")]
+    InvalidWebAuthnAssertion,
+    #[msg("Missing secp256r1 signature verification instruction.")]
+    MissingSecp256r1Verification,
+    #[msg("Invalid hash lock.")]
+    InvalidHashLock,
+    #[msg("The escrow cannot be claimed with this preimage.")]
+    CannotClaimWithPreimage,
+    #[msg("The payment attester is not active.")]
+    InactivePaymentAttester,
+    #[msg("The split basis points must be between 0 and 10000.")]
+    InvalidSplitBps,
+    #[msg("Only the buyer or the seller can submit evidence.")]
+    UnauthorizedEvidenceSubmitter,
+    #[msg("The evidence URI is too long.")]
+    EvidenceUriTooLong,
+    #[msg("Too many evidence entries for this dispute.")]
+    TooManyEvidenceEntries,
+    #[msg("The arbiter registry is full.")]
+    ArbiterRegistryFull,
+    #[msg("The signer is not an active arbiter.")]
+    UnauthorizedArbiter,
+    #[msg(
+        "Panel disputes can only be resolved by the fallback arbiter after the voting deadline."
+    )]
+    PanelDisputeNotEscalated,
+    #[msg("The dispute is not ruled by a panel.")]
+    NotPanelDispute,
+    #[msg("The panel voting period is over.")]
+    PanelVotingClosed,
+    #[msg("The arbiter has already voted on this dispute.")]
+    AlreadyVoted,
+    #[msg("The panel has no more seats available.")]
+    PanelFull,
+    #[msg("Invalid panel configuration.")]
+    InvalidPanelConfig,
+    #[msg("The arbiter is not registered.")]
+    ArbiterNotRegistered,
+    #[msg("The arbiter stake is unbonding.")]
+    ArbiterUnbonding,
+    #[msg("The unbonding period is not over yet.")]
+    UnbondingNotFinished,
+    #[msg("No arbiter rewards to claim.")]
+    NoArbiterRewards,
+    #[msg("Invalid arbiter configuration.")]
+    InvalidArbiterConfig,
+    #[msg("The default judgment cannot be claimed yet.")]
+    CannotClaimDefaultJudgment,
+    #[msg("The resolution deadline has not passed yet.")]
+    ResolutionDeadlineNotPassed,
+    #[msg("The ruling is waiting for the appeal window.")]
+    RulingAwaitingAppeal,
+    #[msg("The ruling cannot be appealed.")]
+    CannotAppealRuling,
+    #[msg("The appeal window is not over yet.")]
+    AppealWindowNotOver,
+    #[msg("Invalid appeal configuration.")]
+    InvalidAppealConfig,
+    #[msg("Invalid dispute bond configuration.")]
+    InvalidDisputeBondConfig,
+    #[msg("The dispute window is closed.")]
+    DisputeWindowClosed,
+    #[msg("Invalid dispute policy.")]
+    InvalidDisputePolicy,
+    #[msg("The escrow is frozen.")]
+    EscrowFrozen,
+    #[msg("The escrow is not frozen.")]
+    EscrowNotFrozen,
+    #[msg("The cancellation must be approved by both the buyer and the seller.")]
+    MissingCancelApproval,
+    #[msg("The fiat payment deadline has passed.")]
+    FiatDeadlinePassed,
+    #[msg("Invalid fiat deadline extension.")]
+    InvalidFiatDeadlineExtension,
+    #[msg("Invalid buyer bond configuration.")]
+    InvalidBuyerBondConfig,
+    #[msg("Invalid merchant configuration.")]
+    InvalidMerchantConfig,
+    #[msg("Invalid escrow limits.")]
+    InvalidEscrowLimits,
+    #[msg("Escrow amount exceeds the mint limit.")]
+    EscrowLimitExceeded,
+    #[msg("Merchant bond is unbonding.")]
+    MerchantUnbonding,
+    #[msg("Merchant bond cannot be slashed for this dispute.")]
+    MerchantNotSlashable,
+    #[msg("Buyer and seller must be different.")]
+    SelfTrade,
+    #[msg("The buyer or the seller cannot arbitrate their own dispute.")]
+    PartyCannotArbitrate,
+    #[msg("The arbiter is not a member of the dispute panel.")]
+    NotPanelMember,
+    #[msg("The arbiter draw is not pending or its slot has not been produced yet.")]
+    ArbiterDrawNotReady,
+    #[msg("Arbiter cannot be slashed for this dispute.")]
+    ArbiterNotSlashable,
+    #[msg("Invalid resolution policy.")]
+    InvalidResolutionPolicy,
+    #[msg("The frozen escrow must be force settled by another arbiter.")]
+    FreezeNotApproved,
+    #[msg("The merchant has disputes pending.")]
+    MerchantDisputePending,
+    #[msg("Invalid dispute response period.")]
+    InvalidDisputeResponsePeriod,
+    #[msg("Invalid fiat grace period.")]
+    InvalidFiatGracePeriod,
+    #[msg("A token account required by the transfer is missing.")]
+    MissingTokenAccount,
 }
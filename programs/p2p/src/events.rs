@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
 
+use crate::states::{DisputePolicy, Ruling};
+
 #[event]
 pub struct EscrowCreated {
     pub id: u64,
     pub seller: Pubkey,
     pub mint: Pubkey,
     pub amount: u64,
+    pub fiat_deadline: i64,
+    pub verified_merchant: bool, // badge
 }
 
 #[event]
@@ -13,6 +17,8 @@ pub struct MarkEscrowAsPaid {
     pub id: u64,
     pub seller: Pubkey,
     pub buyer: Pubkey,
+    pub payment_reference: [u8; 32],
+    pub receipt_hash: [u8; 32],
     pub marked_at: i64,
 }
 
@@ -25,6 +31,28 @@ pub struct TokensReleased {
     pub amount: u64,
 }
 
+#[event]
+pub struct EscrowClaimedWithPreimage {
+    pub id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub preimage: [u8; 32],
+}
+
+#[event]
+pub struct AttestedRelease {
+    pub id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub attester: Pubkey,
+    pub fiat_amount: u64,
+    pub reference: [u8; 32],
+}
+
 #[event]
 pub struct EscrowCancelled {
     pub id: u64,
@@ -38,12 +66,344 @@ pub struct EscrowCancelled {
 pub struct DisputeCreated {
     pub id: u64,
     pub disputant: Pubkey,
+    pub deposit: u64,                 // lamports, or tokens of the deposit mint
+    pub deposit_mint: Option<Pubkey>, // none when paid in lamports
+    pub panel: bool,
     pub disputed_at: i64,
 }
 
+#[event]
+pub struct EvidenceSubmitted {
+    pub id: u64,
+    pub submitted_by: Pubkey,
+    pub index: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct ArbiterAssigned {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub assigned_at: i64,
+}
+
+#[event]
+pub struct ArbiterDrawScheduled {
+    pub id: u64,
+    pub draw_slot: u64,
+}
+
+#[event]
+pub struct ArbiterDrawExpired {
+    pub id: u64,
+    pub fallback_arbiter: Pubkey,
+    pub expired_at: i64,
+}
+
+#[event]
+pub struct PanelSelected {
+    pub id: u64,
+    pub members: Vec<Pubkey>,
+    pub quorum: u8,
+    pub selected_at: i64,
+}
+
+#[event]
+pub struct DisputeVoteCast {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub buyer_bps: u16,
+    pub refund_buyer_deposit: bool,
+    pub refund_seller_deposit: bool,
+    pub votes: u8, // matching votes for this ruling
+    pub voted_at: i64,
+}
+
+#[event]
+pub struct DefaultJudgmentClaimed {
+    pub id: u64,
+    pub disputant: Pubkey,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub fee: u64,
+    pub deposit_refunded: u64, // lamports
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct RulingRecorded {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub buyer_bps: u16,
+    pub refund_buyer_deposit: bool,
+    pub refund_seller_deposit: bool,
+    pub appeal_deadline: i64,
+}
+
+#[event]
+pub struct RulingAppealed {
+    pub id: u64,
+    pub appellant: Pubkey,
+    pub bond: u64, // lamports, or tokens of the deposit mint
+    pub voting_deadline: i64,
+}
+
+#[event]
+pub struct AppealSettled {
+    pub id: u64,
+    pub appellant: Pubkey,
+    pub bond: u64, // lamports, or tokens of the deposit mint, refunded if overturned
+    pub overturned: bool,
+}
+
+#[event]
+pub struct ResolutionDeadlineBreached {
+    pub id: u64,
+    pub assigned_arbiter: Option<Pubkey>,
+    pub resolution_deadline: i64,
+    pub resolved_by: Option<Pubkey>, // none when the default ruling is executed
+    pub breached_at: i64,
+}
+
 #[event]
 pub struct DisputeResolved {
     pub id: u64,
-    pub winner: Pubkey,
+    pub arbiter: Option<Pubkey>, // none when the default ruling is executed
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub buyer_bps: u16,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub fee: u64,
+    pub buyer_deposit_refunded: u64, // lamports, or tokens of the deposit mint
+    pub seller_deposit_refunded: u64, // lamports, or tokens of the deposit mint
+    pub forfeited_lamports: u64,
+    pub forfeited_tokens: u64, // token deposits, added to the mint vault
+    pub arbiter_rewards: u64,  // lamports, part of the forfeited ones
+    pub arbiter_token_rewards: u64, // part of the forfeited tokens
     pub resolved_at: i64,
 }
+
+#[event]
+pub struct SignerSetUpdated {
+    pub owner: Pubkey,
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+#[event]
+pub struct PaymentAttesterUpdated {
+    pub attester: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct ArbiterUpdated {
+    pub arbiter: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct PanelThresholdUpdated {
+    pub mint: Pubkey,
+    pub panel_threshold: u64,
+}
+
+#[event]
+pub struct DisputeBondUpdated {
+    pub mint: Pubkey,
+    pub bond_bps: u16,
+    pub floor: u64,
+    pub cap: u64,
+}
+
+#[event]
+pub struct EscrowFrozen {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub reason: u8,
+    pub frozen_at: i64,
+}
+
+#[event]
+pub struct EscrowUnfrozen {
+    pub id: u64,
+    pub authority: Pubkey,
+    pub frozen_secs: i64,
+    pub unfrozen_at: i64,
+}
+
+#[event]
+pub struct EscrowForceCancelled {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub reason: u8,
+    pub seller: Pubkey,
+    pub returned_amount: u64,
+    pub deposits_refunded: u64, // lamports, or tokens of the deposit mint
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct EscrowForceReleased {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub reason: u8,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub deposits_refunded: u64, // lamports, or tokens of the deposit mint
+    pub released_at: i64,
+}
+
+#[event]
+pub struct EscrowMutuallyCancelled {
+    pub id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub returned_amount: u64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct EscrowAbandoned {
+    pub id: u64,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    pub returned_amount: u64,
+    pub abandoned_at: i64,
+}
+
+#[event]
+pub struct FiatDeadlineExtended {
+    pub id: u64,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub fiat_deadline: i64,
+    pub extended_at: i64,
+}
+
+#[event]
+pub struct BuyerBondUpdated {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub in_lamports: bool,
+    pub cut_bps: u16,
+}
+
+#[event]
+pub struct BuyerBondPosted {
+    pub id: u64,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub in_lamports: bool,
+}
+
+#[event]
+pub struct BuyerBondSettled {
+    pub id: u64,
+    pub buyer: Pubkey,
+    pub returned: u64,
+    pub forfeited: u64, // to the seller
+    pub protocol_cut: u64,
+    pub in_lamports: bool,
+}
+
+#[event]
+pub struct ArbiterStakeUpdated {
+    pub arbiter: Pubkey,
+    pub stake: u64, // lamports
+    pub unbonding_until: i64,
+}
+
+#[event]
+pub struct ArbiterRewardsClaimed {
+    pub arbiter: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ArbiterTokenRewardsClaimed {
+    pub arbiter: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ArbiterSlashed {
+    pub id: u64,
+    pub arbiter: Pubkey,
+    pub lamports: u64,
+    pub remaining_stake: u64,
+}
+
+#[event]
+pub struct MerchantBondUpdated {
+    pub merchant: Pubkey,
+    pub bond: u64,
+    pub unbonding_until: i64,
+    pub verified: bool,
+}
+
+#[event]
+pub struct MerchantBondSlashed {
+    pub id: u64,
+    pub merchant: Pubkey,
+    pub buyer: Pubkey,
+    pub arbiter: Pubkey,
+    pub lamports: u64,
+    pub remaining_bond: u64,
+}
+
+#[event]
+pub struct EscrowLimitsUpdated {
+    pub mint: Pubkey,
+    pub escrow_limit: u64,
+    pub merchant_escrow_limit: u64,
+}
+
+#[event]
+pub struct DisputePolicyUpdated {
+    pub dispute_policy: DisputePolicy,
+}
+
+#[event]
+pub struct ResolutionPolicyUpdated {
+    pub resolution_sla_secs: i64,
+    pub fallback_arbiter: Pubkey,
+    pub default_ruling: Ruling,
+}
+
+#[event]
+pub struct AppealPolicyUpdated {
+    pub appeal_window_secs: i64,
+    pub appeal_bond_bps: u16,
+}
+
+#[event]
+pub struct ArbiterConfigUpdated {
+    pub min_arbiter_stake: u64, // lamports
+    pub arbiter_reward_bps: u16,
+}
+
+#[event]
+pub struct PanelConfigUpdated {
+    pub panel_size: u8,
+    pub panel_quorum: u8,
+    pub panel_voting_secs: i64,
+}
+
+#[event]
+pub struct FiatGracePeriodUpdated {
+    pub fiat_grace_secs: i64,
+}
+
+#[event]
+pub struct MerchantConfigUpdated {
+    pub merchant_min_bond: u64, // lamports
+    pub merchant_fee_bps: u16,
+}
@@ -0,0 +1,145 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::{DISPUTE_SEED, DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED},
+    errors::P2pError,
+    events,
+    states::{Dispute, DisputeOutcome, Escrow, EscrowState, GlobalConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct AppealRuling<'info> {
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = mint,
+        constraint = matches!(escrow.state, EscrowState::ReDispute(_)) @ P2pError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // required when the dispute deposits were paid in the escrow mint (like the appeal bond)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = appellant,
+        associated_token::token_program = token_program,
+    )]
+    pub appellant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = dispute_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub dispute_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AppealRuling<'info> {
+    pub fn appeal_ruling(&mut self, _escrow_id: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.dispute.can_appeal(&self.appellant.key(), now),
+            P2pError::CannotAppealRuling
+        );
+
+        // the appeal bond is a multiple of the appellant's dispute deposit, in the same mint,
+        // and at least one unit more (free or rounded down deposits)
+        let deposit = self.dispute.deposit_of(&self.appellant.key());
+        let bond = (deposit as u128 * self.global_config.appeal_bond_bps as u128 / 10_000) as u64;
+        let bond = bond.max(deposit.checked_add(1).unwrap());
+
+        // deposit appeal bond
+        if self.dispute.deposit_mint.is_some() {
+            let (Some(appellant_ata), Some(dispute_vault_ata)) =
+                (self.appellant_ata.as_ref(), self.dispute_vault_ata.as_ref())
+            else {
+                return Err(P2pError::MissingTokenAccount.into());
+            };
+
+            let cpi_accounts = anchor_spl::token::Transfer {
+                from: appellant_ata.to_account_info(),
+                to: dispute_vault_ata.to_account_info(),
+                authority: self.appellant.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+            anchor_spl::token::transfer(cpi_ctx, bond)?;
+        } else {
+            let cpi_accounts = system_program::Transfer {
+                from: self.appellant.to_account_info(),
+                to: self.dispute_vault.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            system_program::transfer(cpi_ctx, bond)?;
+        }
+
+        // escalate to a panel of arbiters
+        let voting_deadline = now
+            .checked_add(self.global_config.panel_voting_secs)
+            .unwrap();
+
+        self.dispute.appellant = Some(self.appellant.key());
+        self.dispute.appealed_arbiter = self.dispute.arbiter;
+        self.dispute.appeal_bond = bond;
+        self.dispute.panel = true;
+        self.dispute.votes.clear();
+        self.dispute.voting_deadline = voting_deadline;
+        self.dispute.resolution_deadline = voting_deadline
+            .checked_add(self.global_config.resolution_sla_secs)
+            .unwrap();
+
+        // the panel is drawn with `assign_arbiter`, never including the appealed arbiter
+        self.dispute.schedule_draw(Clock::get()?.slot);
+
+        emit!(events::ArbiterDrawScheduled {
+            id: self.escrow.id,
+            draw_slot: self.dispute.draw_slot,
+        });
+
+        // emit event
+        emit!(events::RulingAppealed {
+            id: self.escrow.id,
+            appellant: self.appellant.key(),
+            bond,
+            voting_deadline,
+        });
+
+        Ok(())
+    }
+}
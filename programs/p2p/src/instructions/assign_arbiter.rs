@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use solana_sdk_ids::sysvar::slot_hashes;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, DISPUTE_SEED, GLOBAL_CONFIG_SEED},
+    errors::P2pError,
+    events,
+    states::{ArbiterRegistry, Dispute, DisputeOutcome, GlobalConfig},
+    utils,
+};
+
+// permissionless, draws the arbiter (or the panel members) once the scheduled slot is produced
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct AssignArbiter<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+        constraint = dispute.is_draw_pending() @ P2pError::ArbiterDrawNotReady,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: may not be initialized yet (no arbiters registered), deserialized manually
+    #[account(
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump,
+    )]
+    pub arbiter_registry: UncheckedAccount<'info>,
+
+    /// CHECK: SlotHashes sysvar, read manually (too big to deserialize)
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+impl<'info> AssignArbiter<'info> {
+    pub fn assign_arbiter(&mut self, _escrow_id: u64) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            clock.slot > self.dispute.draw_slot,
+            P2pError::ArbiterDrawNotReady
+        );
+
+        let Some(seed) = utils::slot_hash_seed(
+            &self.slot_hashes.to_account_info(),
+            self.dispute.draw_slot,
+            self.dispute.escrow_id,
+        )?
+        else {
+            // the slot hash is not available anymore, a later slot would let a party re-roll
+            // the draw by waiting, the fallback arbiter gets the dispute instead (panels have
+            // no members, the fallback arbiter resolves them after the voting deadline)
+            self.dispute.draw_slot = 0;
+
+            let fallback_arbiter = self.global_config.fallback_arbiter;
+
+            if !self.dispute.panel {
+                self.dispute.assigned_arbiter = Some(fallback_arbiter);
+            }
+
+            // emit event
+            emit!(events::ArbiterDrawExpired {
+                id: self.dispute.escrow_id,
+                fallback_arbiter,
+                expired_at: clock.unix_timestamp,
+            });
+
+            return Ok(());
+        };
+
+        self.dispute.draw_slot = 0;
+
+        // no arbiters registered yet, the authority resolves the dispute
+        if self.arbiter_registry.data_is_empty() {
+            return Ok(());
+        }
+
+        let registry =
+            ArbiterRegistry::try_deserialize(&mut &self.arbiter_registry.try_borrow_data()?[..])?;
+        let min_stake = self.global_config.min_arbiter_stake;
+
+        // never the parties, nor the arbiter of an appealed ruling
+        let mut excluded = vec![self.dispute.buyer, self.dispute.seller];
+        excluded.extend(self.dispute.arbiter);
+
+        if self.dispute.panel {
+            self.dispute.panel_members =
+                registry.pick_panel(seed, min_stake, self.dispute.panel_size, &excluded);
+
+            // emit event
+            emit!(events::PanelSelected {
+                id: self.dispute.escrow_id,
+                members: self.dispute.panel_members.clone(),
+                quorum: self.dispute.panel_quorum,
+                selected_at: clock.unix_timestamp,
+            });
+        } else if let Some(arbiter) = registry.pick(seed, min_stake, &excluded) {
+            self.dispute.assigned_arbiter = Some(arbiter);
+
+            // emit event
+            emit!(events::ArbiterAssigned {
+                id: self.dispute.escrow_id,
+                arbiter,
+                assigned_at: clock.unix_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
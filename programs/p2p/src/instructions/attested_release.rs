@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{
+        DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED,
+        PAYMENT_ATTESTER_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{Escrow, GlobalConfig, MintVault, PaymentAttester, UserStats},
+    utils::{
+        settle_buyer_bond, transfer_from_mint_vault, verify_ed25519_signature, BuyerBondAccounts,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct AttestedRelease<'info> {
+    // permissionless, the attester signature authorizes the release
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [PAYMENT_ATTESTER_SEED, payment_attester.attester.as_ref()],
+        bump = payment_attester.bump,
+        constraint = payment_attester.active @ P2pError::InactivePaymentAttester,
+    )]
+    pub payment_attester: Account<'info, PaymentAttester>,
+
+    // buyer bond, lamports
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = seller,
+        has_one = buyer,
+        has_one = mint,
+        constraint = escrow.can_attested_release() @ P2pError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AttestedRelease<'info> {
+    pub fn attested_release(
+        &mut self,
+        _escrow_id: u64,
+        fiat_amount: u64,
+        reference: [u8; 32],
+        signature: [u8; 64],
+        bumps: &AttestedReleaseBumps,
+    ) -> Result<()> {
+        // verify attestation
+        let message =
+            PaymentAttester::attestation_message(&self.escrow.key(), fiat_amount, &reference);
+
+        verify_ed25519_signature(
+            &self.payment_attester.attester,
+            &signature,
+            message.as_bytes(),
+        )?;
+
+        // transfer tokens to buyer ata
+        let fee = self.escrow.calculate_fee();
+
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.buyer_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            self.escrow.amount.checked_sub(fee).unwrap(),
+        )?;
+
+        // update available amount to withdraw in mint vault
+        self.mint_vault.add_available_amount(fee);
+
+        // the buyer bond is returned
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: None,
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump: bumps.dispute_vault,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            false,
+        )?;
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_trade(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
+        // emit event
+        emit!(events::AttestedRelease {
+            id: self.escrow.id,
+            seller: self.seller.key(),
+            buyer: self.buyer.key(),
+            mint: self.mint.key(),
+            amount: self.escrow.amount,
+            attester: self.payment_attester.attester,
+            fiat_amount,
+            reference,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED, USER_STATS_SEED},
+    errors::P2pError,
+    events,
+    states::{Escrow, EscrowState, GlobalConfig, MintVault, UserStats},
+    utils::transfer_from_mint_vault,
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct BuyerAbandon<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = mint,
+        constraint = matches!(escrow.state, EscrowState::Open(_)) @ P2pError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyerAbandon<'info> {
+    pub fn buyer_abandon(&mut self, _escrow_id: u64, bumps: &BuyerAbandonBumps) -> Result<()> {
+        // transfer tokens back to seller right away (amount and deposited fee)
+        let fee = self.escrow.calculate_fee();
+        let total_amount = self.escrow.amount.checked_add(fee).unwrap();
+
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.seller_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            total_amount,
+        )?;
+
+        // counted against the buyer
+        self.buyer_stats
+            .init_if_needed(self.buyer.key(), bumps.buyer_stats);
+        self.buyer_stats.record_abandon();
+
+        // emit event
+        emit!(events::EscrowAbandoned {
+            id: self.escrow.id,
+            buyer: self.buyer.key(),
+            seller: self.seller.key(),
+            mint: self.mint.key(),
+            returned_amount: total_amount,
+            abandoned_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -5,10 +5,11 @@ use anchor_spl::{
 };
 
 use crate::{
-    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED, USER_STATS_SEED},
     errors::P2pError,
     events,
-    states::{Escrow, GlobalConfig, MintVault},
+    states::{Escrow, GlobalConfig, MintVault, UserStats},
+    utils::transfer_from_mint_vault,
 };
 
 #[derive(Accounts)]
@@ -30,7 +31,7 @@ pub struct CancelEscrow<'info> {
         bump = escrow.bump,
         has_one = seller,
         has_one = mint,
-        constraint  = escrow.can_cancel(global_config.fiat_deadline_secs) @ P2pError::CannotCancelEscrow,
+        constraint  = escrow.can_cancel(global_config.fiat_grace_secs) @ P2pError::CannotCancelEscrow,
     )]
     pub escrow: Account<'info, Escrow>,
 
@@ -60,33 +61,49 @@ pub struct CancelEscrow<'info> {
     )]
     pub seller_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, escrow.buyer.as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> CancelEscrow<'info> {
-    pub fn cancel_escrow(&self, _escrow_id: u64) -> Result<()> {
+    pub fn cancel_escrow(&mut self, _escrow_id: u64, bumps: &CancelEscrowBumps) -> Result<()> {
         // transfer tokens back to seller
-        let mint_key = self.mint.key();
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[MINT_VAULT_SEED, mint_key.as_ref(), &[self.mint_vault.bump]]];
-
-        let cpi_accounts = anchor_spl::token::Transfer {
-            from: self.mint_vault_ata.to_account_info(),
-            to: self.seller_ata.to_account_info(),
-            authority: self.mint_vault.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-
-        let fee = self.global_config.calculate_fee(self.escrow.amount);
+        let fee = self.escrow.calculate_fee();
         let total_amount = self.escrow.amount.checked_add(fee).unwrap();
 
-        anchor_spl::token::transfer(cpi_ctx, total_amount)?;
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.seller_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            total_amount,
+        )?;
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_cancellation(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
 
         // emit event
         emit!(events::EscrowCancelled {
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, DISPUTE_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::ArbiterRegistry,
+    utils::transfer_from_dispute_vault,
+};
+
+#[derive(Accounts)]
+pub struct ClaimArbiterRewards<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimArbiterRewards<'info> {
+    pub fn claim_arbiter_rewards(&mut self, dispute_vault_bump: u8) -> Result<()> {
+        let entry = self
+            .arbiter_registry
+            .get_mut(&self.arbiter.key())
+            .ok_or(P2pError::ArbiterNotRegistered)?;
+
+        let lamports = entry.rewards;
+
+        require!(lamports > 0, P2pError::NoArbiterRewards);
+
+        entry.rewards = 0;
+
+        // transfer rewards from the dispute vault
+        transfer_from_dispute_vault(
+            self.dispute_vault.to_account_info(),
+            self.arbiter.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
+            dispute_vault_bump,
+        )?;
+
+        // emit event
+        emit!(events::ArbiterRewardsClaimed {
+            arbiter: self.arbiter.key(),
+            lamports,
+        });
+
+        Ok(())
+    }
+}
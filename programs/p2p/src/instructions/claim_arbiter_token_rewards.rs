@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::MINT_VAULT_SEED, errors::P2pError, events, states::MintVault,
+    utils::transfer_from_mint_vault,
+};
+
+// share of the forfeited token deposits, one mint at a time
+#[derive(Accounts)]
+pub struct ClaimArbiterTokenRewards<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint,
+        associated_token::authority = arbiter,
+        associated_token::token_program = token_program,
+    )]
+    pub arbiter_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimArbiterTokenRewards<'info> {
+    pub fn claim_arbiter_token_rewards(&mut self) -> Result<()> {
+        let amount = self.mint_vault.take_arbiter_reward(&self.arbiter.key());
+
+        require!(amount > 0, P2pError::NoArbiterRewards);
+
+        // transfer rewards from the mint vault ata
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.arbiter_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            amount,
+        )?;
+
+        // emit event
+        emit!(events::ArbiterTokenRewardsClaimed {
+            arbiter: self.arbiter.key(),
+            mint: self.mint.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{
+        DISPUTE_SEED, DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MERCHANT_PROFILE_SEED,
+        MINT_VAULT_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{
+        Dispute, DisputeOutcome, Escrow, EscrowDisputedBy, GlobalConfig, MerchantProfile,
+        MintVault, UserStats,
+    },
+    utils::{
+        settle_buyer_bond, transfer_from_dispute_vault, transfer_from_mint_vault,
+        transfer_tokens_from_dispute_vault, BuyerBondAccounts,
+    },
+};
+
+// permissionless, anyone can execute the default judgment in favor of the disputant
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ClaimDefaultJudgment<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = mint,
+        constraint = escrow.can_claim_default_judgment(
+            &global_config.dispute_policy.re_dispute,
+            global_config.dispute_response_secs,
+        ) @ P2pError::CannotClaimDefaultJudgment,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // required when the dispute deposit was paid in the escrow mint (created with the dispute)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = dispute_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub dispute_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    /// CHECK: may not exist (the seller never staked a bond), read and written manually
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub merchant_profile: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimDefaultJudgment<'info> {
+    pub fn claim_default_judgment(
+        &mut self,
+        _escrow_id: u64,
+        bumps: &ClaimDefaultJudgmentBumps,
+    ) -> Result<()> {
+        // the whole escrow goes to the disputant
+        let (disputant, disputant_ata, buyer_bps) = match self.escrow.disputed_by {
+            EscrowDisputedBy::Buyer => (
+                self.buyer.to_account_info(),
+                self.buyer_ata.to_account_info(),
+                10_000,
+            ),
+            EscrowDisputedBy::Seller => (
+                self.seller.to_account_info(),
+                self.seller_ata.to_account_info(),
+                0,
+            ),
+            EscrowDisputedBy::Nobody => return Err(P2pError::InvalidEscrowState.into()),
+        };
+
+        // refund the disputant deposit (the only one made, there was no re-dispute)
+        let deposit_refunded = self
+            .dispute
+            .buyer_deposit
+            .checked_add(self.dispute.seller_deposit)
+            .unwrap();
+
+        if deposit_refunded > 0 && self.dispute.deposit_mint.is_some() {
+            transfer_tokens_from_dispute_vault(
+                self.dispute_vault.to_account_info(),
+                self.dispute_vault_ata.as_ref(),
+                disputant_ata,
+                self.token_program.to_account_info(),
+                deposit_refunded,
+                bumps.dispute_vault,
+            )?;
+        } else if deposit_refunded > 0 {
+            transfer_from_dispute_vault(
+                self.dispute_vault.to_account_info(),
+                disputant.clone(),
+                self.system_program.to_account_info(),
+                deposit_refunded,
+                bumps.dispute_vault,
+            )?;
+        }
+
+        // transfer tokens (the buyer pays the fee, the seller gets it back)
+        let (buyer_amount, seller_amount, fee) = self.escrow.calculate_split(buyer_bps);
+
+        if buyer_amount > 0 {
+            self.transfer_tokens(self.buyer_ata.to_account_info(), buyer_amount)?;
+        }
+
+        if seller_amount > 0 {
+            self.transfer_tokens(self.seller_ata.to_account_info(), seller_amount)?;
+        }
+
+        // update available amount in mint_vault
+        self.mint_vault.add_available_amount(fee);
+
+        // forfeited if the seller disputed and the buyer did not respond
+        self.settle_buyer_bond(buyer_bps == 0, bumps.dispute_vault)?;
+
+        // record outcome
+        let now = Clock::get()?.unix_timestamp;
+
+        self.dispute.outcome = DisputeOutcome::Resolved {
+            buyer_bps,
+            buyer_deposit_refunded: self.dispute.buyer_deposit,
+            seller_deposit_refunded: self.dispute.seller_deposit,
+            resolved_at: now,
+        };
+
+        // the merchant bond can be unstaked once its disputes are settled
+        if self.dispute.verified_merchant {
+            MerchantProfile::record_pending_dispute(&self.merchant_profile, false)?;
+        }
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_dispute_outcome(
+            &mut self.buyer_stats,
+            &mut self.seller_stats,
+            &self.escrow,
+            buyer_bps,
+        );
+
+        // emit event
+        emit!(events::DefaultJudgmentClaimed {
+            id: self.escrow.id,
+            disputant: disputant.key(),
+            buyer_amount,
+            seller_amount,
+            fee,
+            deposit_refunded,
+            claimed_at: now,
+        });
+
+        Ok(())
+    }
+
+    fn settle_buyer_bond(&mut self, forfeited: bool, dispute_vault_bump: u8) -> Result<()> {
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: Some(self.seller_ata.to_account_info()),
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            forfeited,
+        )
+    }
+
+    fn transfer_tokens(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            to,
+            self.token_program.to_account_info(),
+            amount,
+        )
+    }
+}
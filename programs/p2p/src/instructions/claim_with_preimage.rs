@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED, USER_STATS_SEED},
+    errors::P2pError,
+    events,
+    states::{Escrow, GlobalConfig, MintVault, UserStats},
+    utils::transfer_from_mint_vault,
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64, preimage: [u8; 32])]
+pub struct ClaimWithPreimage<'info> {
+    // anyone who knows the preimage can claim on behalf of the buyer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = seller,
+        has_one = buyer,
+        has_one = mint,
+        constraint = escrow.can_claim_with_preimage(&preimage) @ P2pError::CannotClaimWithPreimage,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimWithPreimage<'info> {
+    pub fn claim_with_preimage(
+        &mut self,
+        _escrow_id: u64,
+        preimage: [u8; 32],
+        bumps: &ClaimWithPreimageBumps,
+    ) -> Result<()> {
+        // transfer tokens to buyer ata
+        let fee = self.escrow.calculate_fee();
+
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.buyer_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            self.escrow.amount.checked_sub(fee).unwrap(),
+        )?;
+
+        // update available amount to withdraw in mint vault
+        self.mint_vault.add_available_amount(fee);
+
+        // update the reputation of both parties (the buyer's only if it claimed)
+        if self.payer.key() == self.escrow.buyer {
+            self.escrow.buyer_acted = true;
+        }
+
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_trade(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
+        // emit event (preimage included so the counterparty chain can be settled)
+        emit!(events::EscrowClaimedWithPreimage {
+            id: self.escrow.id,
+            seller: self.seller.key(),
+            buyer: self.buyer.key(),
+            mint: self.mint.key(),
+            amount: self.escrow.amount,
+            preimage,
+        });
+
+        Ok(())
+    }
+}
@@ -1,9 +1,21 @@
 use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 use crate::{
-    constants::{DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED},
+    constants::{
+        DISPUTE_EVIDENCE_SEED, DISPUTE_SEED, DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED,
+        MERCHANT_PROFILE_SEED, MINT_VAULT_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
     events,
-    states::{Escrow, GlobalConfig},
+    states::{
+        Dispute, DisputeEvidence, DisputeOutcome, Escrow, EscrowState, GlobalConfig,
+        MerchantProfile, MintVault, UserStats,
+    },
+    utils::post_buyer_bond,
 };
 
 #[derive(Accounts)]
@@ -21,10 +33,39 @@ pub struct CreateDispute<'info> {
     #[account(
         mut,
         seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
-        bump = escrow.bump
+        bump = escrow.bump,
+        has_one = mint,
+        // hash locked escrows settle with the preimage or the refund after expiry
+        constraint = !escrow.is_hash_locked() @ P2pError::CannotDisputeEscrow,
     )]
     pub escrow: Account<'info, Escrow>,
 
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        init_if_needed,
+        payer = disputant,
+        space = Dispute::SIZE,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        init_if_needed,
+        payer = disputant,
+        space = DisputeEvidence::SIZE,
+        seeds = [DISPUTE_EVIDENCE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub dispute_evidence: Account<'info, DisputeEvidence>,
+
     #[account(
         mut,
         seeds = [DISPUTE_VAULT_SEED],
@@ -32,32 +73,231 @@ pub struct CreateDispute<'info> {
     )]
     pub dispute_vault: SystemAccount<'info>,
 
+    // required only when the dispute bond is paid in the escrow mint
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = disputant,
+        associated_token::token_program = token_program,
+    )]
+    pub disputant_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // per mint dispute vault for the token bonds, same as above
+    #[account(
+        init_if_needed,
+        payer = disputant,
+        associated_token::mint = mint,
+        associated_token::authority = dispute_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub dispute_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // buyer bond in the escrow mint, posted by a dispute of an open escrow (same as above)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = disputant,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, disputant.key().as_ref()],
+        bump,
+    )]
+    pub disputant_stats: Account<'info, UserStats>,
+
+    /// CHECK: may not exist (the seller never staked a bond), read and written manually
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, escrow.seller.as_ref()],
+        bump,
+    )]
+    pub merchant_profile: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateDispute<'info> {
-    pub fn create_dispute(&mut self, _escrow_id: u64) -> Result<()> {
+    pub fn create_dispute(&mut self, _escrow_id: u64, bumps: &CreateDisputeBumps) -> Result<()> {
+        // an open escrow was never marked as paid, the buyer bond is posted with the dispute
+        let posts_buyer_bond = matches!(self.escrow.state, EscrowState::Open(_));
+
         // update escrow state (checks inside)
-        self.escrow.dispute(
-            self.global_config.dispute_deadline_secs,
-            self.disputant.key(),
-        )?;
-
-        // deposit fee escrow
-        let cpi_accounts = system_program::Transfer {
-            from: self.disputant.to_account_info(),
-            to: self.dispute_vault.to_account_info(),
+        self.escrow
+            .dispute(&self.global_config.dispute_policy, self.disputant.key())?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        if self.disputant.key() == self.escrow.buyer {
+            self.escrow.buyer_acted = true;
+        }
+
+        // record dispute (first dispute) or re-dispute
+        if matches!(self.escrow.state, EscrowState::Dispute(_)) {
+            self.dispute.set_inner(Dispute {
+                escrow_id: self.escrow.id,
+                escrow: self.escrow.key(),
+                buyer: self.escrow.buyer,
+                seller: self.escrow.seller,
+                escrow_amount: self.escrow.amount,
+                verified_merchant: self.escrow.verified_merchant,
+                opened_by: self.disputant.key(),
+                opened_at: now,
+                redisputed_at: None,
+                buyer_deposit: 0,
+                seller_deposit: 0,
+                deposit_mint: self
+                    .mint_vault
+                    .calculate_dispute_bond(self.escrow.amount)
+                    .map(|_| self.mint.key()),
+                assigned_arbiter: None,
+                draw_slot: 0,
+                arbiter: None,
+                outcome: DisputeOutcome::Pending,
+                panel: self.mint_vault.requires_panel(self.escrow.amount),
+                panel_size: self.global_config.panel_size,
+                panel_quorum: self.global_config.panel_quorum,
+                panel_members: Vec::new(),
+                voting_deadline: 0,
+                resolution_deadline: 0,
+                votes: Vec::new(),
+                pending_ruling: None,
+                appeal_deadline: 0,
+                appellant: None,
+                appealed_arbiter: None,
+                appeal_bond: 0,
+                merchant_slashed: 0,
+                force_settled: false,
+                frozen_at: 0,
+                slashed_arbiters: Vec::new(),
+                bump: bumps.dispute,
+            });
+
+            self.disputant_stats
+                .init_if_needed(self.disputant.key(), bumps.disputant_stats);
+            self.disputant_stats.record_dispute_opened();
+
+            // the merchant can't unbond until the dispute is settled
+            if self.escrow.verified_merchant {
+                MerchantProfile::record_pending_dispute(&self.merchant_profile, true)?;
+            }
+
+            self.dispute_evidence.set_inner(DisputeEvidence {
+                escrow_id: self.escrow.id,
+                bump: bumps.dispute_evidence,
+                entries: Vec::new(),
+            });
+        } else {
+            self.dispute.redisputed_at = Some(now);
+
+            // the panel starts voting once the dispute goes to the arbiters, the arbiter
+            // (or the panel members) are drawn later with `assign_arbiter`
+            if self.dispute.panel {
+                self.dispute.voting_deadline = now
+                    .checked_add(self.global_config.panel_voting_secs)
+                    .unwrap();
+            }
+
+            self.dispute.schedule_draw(Clock::get()?.slot);
+
+            emit!(events::ArbiterDrawScheduled {
+                id: self.escrow.id,
+                draw_slot: self.dispute.draw_slot,
+            });
+
+            // panels get the SLA once the voting period is over
+            self.dispute.resolution_deadline = now
+                .max(self.dispute.voting_deadline)
+                .checked_add(self.global_config.resolution_sla_secs)
+                .unwrap();
+        }
+
+        // deposit fee escrow, in the escrow mint if a token bond was set when the dispute
+        // was opened (the counterparty matches the opener's bond), in lamports otherwise
+        let deposit = if self.dispute.deposit_mint.is_some() {
+            let deposit = match self.dispute.redisputed_at {
+                Some(_) => self
+                    .dispute
+                    .buyer_deposit
+                    .checked_add(self.dispute.seller_deposit)
+                    .unwrap(),
+                None => self
+                    .mint_vault
+                    .calculate_dispute_bond(self.escrow.amount)
+                    .unwrap(),
+            };
+
+            let (Some(disputant_ata), Some(dispute_vault_ata)) =
+                (self.disputant_ata.as_ref(), self.dispute_vault_ata.as_ref())
+            else {
+                return Err(P2pError::MissingTokenAccount.into());
+            };
+
+            let cpi_accounts = anchor_spl::token::Transfer {
+                from: disputant_ata.to_account_info(),
+                to: dispute_vault_ata.to_account_info(),
+                authority: self.disputant.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+
+            anchor_spl::token::transfer(cpi_ctx, deposit)?;
+
+            deposit
+        } else {
+            let deposit = self.global_config.dispute_fee_escrow;
+
+            let cpi_accounts = system_program::Transfer {
+                from: self.disputant.to_account_info(),
+                to: self.dispute_vault.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+            system_program::transfer(cpi_ctx, deposit)?;
+
+            deposit
         };
 
-        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+        self.dispute.record_deposit(self.disputant.key(), deposit);
+
+        if let Some(bond) = self.mint_vault.buyer_bond().filter(|_| posts_buyer_bond) {
+            post_buyer_bond(
+                &bond,
+                self.disputant.to_account_info(),
+                self.disputant_ata.as_ref().map(|ata| ata.to_account_info()),
+                self.dispute_vault.to_account_info(),
+                self.mint_vault_ata
+                    .as_ref()
+                    .map(|ata| ata.to_account_info()),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            )?;
+
+            emit!(events::BuyerBondPosted {
+                id: self.escrow.id,
+                buyer: self.disputant.key(),
+                amount: bond.amount,
+                in_lamports: bond.in_lamports,
+            });
 
-        system_program::transfer(cpi_ctx, self.global_config.dispute_fee_escrow)?;
+            self.escrow.buyer_bond = Some(bond);
+        }
 
         // emit event
         emit!(events::DisputeCreated {
             id: self.escrow.id,
             disputant: self.disputant.key(),
-            disputed_at: Clock::get()?.unix_timestamp,
+            deposit,
+            deposit_mint: self.dispute.deposit_mint,
+            panel: self.dispute.panel,
+            disputed_at: now,
         });
 
         Ok(())
@@ -5,9 +5,15 @@ use anchor_spl::{
 };
 
 use crate::{
-    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    constants::{
+        ESCROW_SEED, GLOBAL_CONFIG_SEED, MERCHANT_PROFILE_SEED, MINT_VAULT_SEED, SIGNER_SET_SEED,
+    },
+    errors::P2pError,
     events,
-    states::{Escrow, EscrowDisputedBy, EscrowState, GlobalConfig, MintVault},
+    states::{
+        Escrow, EscrowDisputedBy, EscrowState, GlobalConfig, MerchantProfile, MintVault,
+        ReleaseApprover, SignerSet,
+    },
 };
 
 #[derive(Accounts)]
@@ -15,6 +21,8 @@ pub struct CreateEscrow<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    // no self trades (the reputation of both parties is updated on settlement)
+    #[account(constraint = buyer.key() != creator.key() @ P2pError::SelfTrade)]
     pub buyer: SystemAccount<'info>,
 
     #[account(
@@ -61,25 +69,67 @@ pub struct CreateEscrow<'info> {
     )]
     pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
 
+    // required only when the release approver is the seller's signer set
+    #[account(
+        seeds = [SIGNER_SET_SEED, creator.key().as_ref()],
+        bump = signer_set.bump,
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+
+    // required only for the verified merchant fee and limit
+    #[account(
+        seeds = [MERCHANT_PROFILE_SEED, creator.key().as_ref()],
+        bump = merchant_profile.bump,
+    )]
+    pub merchant_profile: Option<Account<'info, MerchantProfile>>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateEscrow<'info> {
-    pub fn create_escrow(&mut self, amount: u64, bumps: &CreateEscrowBumps) -> Result<()> {
-        // tranfer tokens to mint vault ata
-        let cpi_account = anchor_spl::token::Transfer {
-            from: self.creator_ata.to_account_info(),
-            to: self.mint_vault_ata.to_account_info(),
-            authority: self.creator.to_account_info(),
-        };
+    pub fn create_escrow(
+        &mut self,
+        amount: u64,
+        release_approver: ReleaseApprover,
+        bumps: &CreateEscrowBumps,
+    ) -> Result<()> {
+        let mut signer_set = None;
+
+        match release_approver {
+            // the set is copied, changing it later doesn't affect this escrow
+            ReleaseApprover::SignerSet => {
+                let seller_signer_set =
+                    self.signer_set.as_ref().ok_or(P2pError::InvalidSignerSet)?;
+
+                signer_set = Some(seller_signer_set.snapshot());
+            }
+            ReleaseApprover::HashLock { expires_at, .. } => {
+                require!(
+                    expires_at > Clock::get()?.unix_timestamp,
+                    P2pError::InvalidHashLock
+                );
+            }
+            _ => {}
+        }
 
-        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_account);
+        // verified merchants get a lower fee and a higher limit
+        let verified_merchant = self
+            .merchant_profile
+            .as_ref()
+            .is_some_and(|profile| profile.is_verified(self.global_config.merchant_min_bond));
 
-        let fee = self.global_config.calculate_fee(amount);
+        // a new mint vault has no limits
+        require!(
+            self.mint_vault.is_within_limit(amount, verified_merchant),
+            P2pError::EscrowLimitExceeded
+        );
 
-        anchor_spl::token::transfer(cpi_ctx, amount.checked_add(fee).unwrap())?;
+        let now = Clock::get()?.unix_timestamp;
+        let fiat_deadline = now
+            .checked_add(self.global_config.fiat_deadline_secs)
+            .unwrap();
 
         // set escrow data
         self.escrow.set_inner(Escrow {
@@ -88,23 +138,39 @@ impl<'info> CreateEscrow<'info> {
             buyer: self.buyer.key(),
             mint: self.mint.key(),
             amount,
-            state: EscrowState::Open(Clock::get()?.unix_timestamp),
+            fee_bps: self.global_config.fee_bps_for(verified_merchant),
+            verified_merchant,
+            state: EscrowState::Open(now),
+            fiat_deadline,
+            payment_proof: None,
+            buyer_acted: false,
+            buyer_bond: None,
             disputed_by: EscrowDisputedBy::Nobody,
+            release_approver,
+            signer_set,
+            freeze: None,
             bump: bumps.escrow,
         });
 
         // increment escrow counter
         self.global_config.increment_escrow_count();
 
+        // tranfer tokens to mint vault ata
+        let cpi_account = anchor_spl::token::Transfer {
+            from: self.creator_ata.to_account_info(),
+            to: self.mint_vault_ata.to_account_info(),
+            authority: self.creator.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_account);
+
+        let fee = self.escrow.calculate_fee();
+
+        anchor_spl::token::transfer(cpi_ctx, amount.checked_add(fee).unwrap())?;
+
         // set mint vault data if not already set
-        if !self.mint_vault.is_initialized {
-            self.mint_vault.set_inner(MintVault {
-                is_initialized: true,
-                mint: self.mint.key(),
-                available_amount: 0, // will be updated on release, not here
-                bump: bumps.mint_vault,
-            });
-        }
+        self.mint_vault
+            .init_if_needed(self.mint.key(), bumps.mint_vault);
 
         // emit event
         emit!(events::EscrowCreated {
@@ -112,6 +178,8 @@ impl<'info> CreateEscrow<'info> {
             seller: self.creator.key(),
             mint: self.mint.key(),
             amount,
+            fiat_deadline,
+            verified_merchant,
         });
 
         Ok(())
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+// glob import, the composite accounts derive also needs the generated client modules
+use super::settle_dispute::*;
+
+use crate::errors::P2pError;
+
+// permissionless once the resolution deadline has passed
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ExecuteDefaultRuling<'info> {
+    pub settlement: SettleDispute<'info>,
+}
+
+impl<'info> ExecuteDefaultRuling<'info> {
+    pub fn execute_default_ruling(
+        &mut self,
+        _escrow_id: u64,
+        bumps: &SettleDisputeBumps,
+    ) -> Result<()> {
+        require!(
+            self.settlement
+                .dispute
+                .is_overdue(Clock::get()?.unix_timestamp),
+            P2pError::ResolutionDeadlineNotPassed
+        );
+
+        let ruling = self.settlement.global_config.default_ruling;
+
+        // no arbiter ruled, nobody is rewarded
+        self.settlement.settle(ruling, None, &[], bumps)
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ESCROW_SEED, MAX_FIAT_DEADLINE_EXTENSION_SECS},
+    errors::P2pError,
+    events,
+    states::{Escrow, EscrowState},
+};
+
+// legitimate payment delays, granted by the seller
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ExtendFiatDeadline<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = seller,
+        constraint = matches!(escrow.state, EscrowState::Open(_)) @ P2pError::EscrowAlreadyTaken,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> ExtendFiatDeadline<'info> {
+    pub fn extend_fiat_deadline(&mut self, _escrow_id: u64, extension_secs: i64) -> Result<()> {
+        require!(
+            extension_secs > 0 && extension_secs <= MAX_FIAT_DEADLINE_EXTENSION_SECS,
+            P2pError::InvalidFiatDeadlineExtension
+        );
+
+        self.escrow.fiat_deadline = self
+            .escrow
+            .fiat_deadline
+            .checked_add(extension_secs)
+            .ok_or(P2pError::InvalidFiatDeadlineExtension)?;
+
+        // emit event
+        emit!(events::FiatDeadlineExtended {
+            id: self.escrow.id,
+            seller: self.seller.key(),
+            buyer: self.escrow.buyer,
+            fiat_deadline: self.escrow.fiat_deadline,
+            extended_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+// glob import, the composite accounts derive also needs the generated client modules
+use super::settle_dispute::*;
+
+use crate::errors::P2pError;
+
+// permissionless once the appeal window is over without an appeal
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct FinalizeRuling<'info> {
+    pub settlement: SettleDispute<'info>,
+}
+
+impl<'info> FinalizeRuling<'info> {
+    pub fn finalize_ruling(&mut self, _escrow_id: u64, bumps: &SettleDisputeBumps) -> Result<()> {
+        let dispute = &self.settlement.dispute;
+
+        require!(dispute.is_awaiting_appeal(), P2pError::InvalidEscrowState);
+        require!(
+            Clock::get()?.unix_timestamp > dispute.appeal_deadline,
+            P2pError::AppealWindowNotOver
+        );
+
+        let ruling = dispute.pending_ruling.unwrap();
+        let arbiter = dispute.arbiter;
+        let rewarded: Vec<Pubkey> = arbiter.into_iter().collect();
+
+        self.settlement.settle(ruling, arbiter, &rewarded, bumps)
+    }
+}
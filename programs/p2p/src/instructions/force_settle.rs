@@ -0,0 +1,345 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{
+        ARBITER_REGISTRY_SEED, DISPUTE_SEED, DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED,
+        MERCHANT_PROFILE_SEED, MINT_VAULT_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{
+        ArbiterRegistry, Dispute, DisputeOutcome, Escrow, EscrowDisputedBy, GlobalConfig,
+        MerchantProfile, MintVault, UserStats,
+    },
+    utils::{
+        settle_buyer_bond, transfer_from_dispute_vault, transfer_from_mint_vault,
+        transfer_tokens_from_dispute_vault, BuyerBondAccounts,
+    },
+};
+
+// accounts shared by `force_cancel` and `force_release` (frozen escrows only, second arbiter)
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct ForceSettle<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+        constraint = arbiter_registry.is_eligible(
+            &arbiter.key(),
+            global_config.min_arbiter_stake,
+        ) @ P2pError::UnauthorizedArbiter,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = mint,
+        constraint = escrow.is_frozen() @ P2pError::EscrowNotFrozen,
+        constraint = escrow.can_force_settle(&arbiter.key()) @ P2pError::FreezeNotApproved,
+        constraint = arbiter.key() != escrow.buyer && arbiter.key() != escrow.seller
+            @ P2pError::UnauthorizedArbiter,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // required when the escrow was frozen during a dispute
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    // required when the dispute deposits were paid in the escrow mint
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = dispute_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub dispute_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    /// CHECK: may not exist (the seller never staked a bond), read and written manually
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub merchant_profile: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ForceSettle<'info> {
+    pub fn force_cancel(
+        &mut self,
+        _escrow_id: u64,
+        reason: u8,
+        forfeit_buyer_bond: bool,
+        bumps: &ForceSettleBumps,
+    ) -> Result<()> {
+        // the seller gets the amount and the deposited fee back
+        let (_, returned_amount, _) = self.escrow.calculate_split(0);
+
+        self.transfer_tokens(self.seller_ata.to_account_info(), returned_amount)?;
+
+        // forfeited if the buyer claimed a payment that was not made
+        self.settle_buyer_bond(forfeit_buyer_bond, bumps.dispute_vault)?;
+
+        let deposits_refunded = self.close_dispute(0, bumps.dispute_vault)?;
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_cancellation(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
+        // emit event
+        emit!(events::EscrowForceCancelled {
+            id: self.escrow.id,
+            arbiter: self.arbiter.key(),
+            reason,
+            seller: self.seller.key(),
+            returned_amount,
+            deposits_refunded,
+            cancelled_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn force_release(
+        &mut self,
+        _escrow_id: u64,
+        reason: u8,
+        bumps: &ForceSettleBumps,
+    ) -> Result<()> {
+        // the buyer gets the amount minus the fee, like a regular release
+        let (amount, _, fee) = self.escrow.calculate_split(10_000);
+
+        self.transfer_tokens(self.buyer_ata.to_account_info(), amount)?;
+
+        self.mint_vault.add_available_amount(fee);
+
+        self.settle_buyer_bond(false, bumps.dispute_vault)?;
+
+        let deposits_refunded = self.close_dispute(10_000, bumps.dispute_vault)?;
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_trade(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
+        // emit event
+        emit!(events::EscrowForceReleased {
+            id: self.escrow.id,
+            arbiter: self.arbiter.key(),
+            reason,
+            buyer: self.buyer.key(),
+            amount,
+            fee,
+            deposits_refunded,
+            released_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    // the dispute deposits (and appeal bond) are refunded, nobody is at fault in a fraud case
+    fn close_dispute(&mut self, buyer_bps: u16, dispute_vault_bump: u8) -> Result<u64> {
+        if self.escrow.disputed_by == EscrowDisputedBy::Nobody {
+            return Ok(0);
+        }
+
+        let Some(dispute) = self.dispute.as_mut() else {
+            return Err(P2pError::InvalidEscrowState.into());
+        };
+
+        let (buyer_deposit, seller_deposit) = (dispute.buyer_deposit, dispute.seller_deposit);
+        let deposit_mint = dispute.deposit_mint;
+
+        // the appeal bond goes back with the appellant's deposit, in the same mint
+        let (mut buyer_refund, mut seller_refund) = (buyer_deposit, seller_deposit);
+
+        match dispute.appellant {
+            Some(appellant) if appellant == dispute.buyer => {
+                buyer_refund = buyer_refund.checked_add(dispute.appeal_bond).unwrap()
+            }
+            Some(_) => seller_refund = seller_refund.checked_add(dispute.appeal_bond).unwrap(),
+            None => {}
+        }
+
+        dispute.arbiter = Some(self.arbiter.key());
+        dispute.outcome = DisputeOutcome::Resolved {
+            buyer_bps,
+            buyer_deposit_refunded: buyer_deposit,
+            seller_deposit_refunded: seller_deposit,
+            resolved_at: Clock::get()?.unix_timestamp,
+        };
+        dispute.force_settled = true;
+
+        // the merchant bond can be unstaked once its disputes are settled
+        if dispute.verified_merchant {
+            MerchantProfile::record_pending_dispute(&self.merchant_profile, false)?;
+        }
+
+        for (to, to_ata, deposit) in [
+            (
+                self.buyer.to_account_info(),
+                self.buyer_ata.to_account_info(),
+                buyer_refund,
+            ),
+            (
+                self.seller.to_account_info(),
+                self.seller_ata.to_account_info(),
+                seller_refund,
+            ),
+        ] {
+            if deposit == 0 {
+                continue;
+            }
+
+            if deposit_mint.is_some() {
+                transfer_tokens_from_dispute_vault(
+                    self.dispute_vault.to_account_info(),
+                    self.dispute_vault_ata.as_ref(),
+                    to_ata,
+                    self.token_program.to_account_info(),
+                    deposit,
+                    dispute_vault_bump,
+                )?;
+            } else {
+                transfer_from_dispute_vault(
+                    self.dispute_vault.to_account_info(),
+                    to,
+                    self.system_program.to_account_info(),
+                    deposit,
+                    dispute_vault_bump,
+                )?;
+            }
+        }
+
+        Ok(buyer_deposit.checked_add(seller_deposit).unwrap())
+    }
+
+    fn settle_buyer_bond(&mut self, forfeited: bool, dispute_vault_bump: u8) -> Result<()> {
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: Some(self.seller_ata.to_account_info()),
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            forfeited,
+        )
+    }
+
+    fn transfer_tokens(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            to,
+            self.token_program.to_account_info(),
+            amount,
+        )
+    }
+}
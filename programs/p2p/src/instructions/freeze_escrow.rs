@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, DISPUTE_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED},
+    errors::P2pError,
+    events,
+    states::{
+        ArbiterRegistry, Dispute, DisputeOutcome, Escrow, EscrowDisputedBy, EscrowFreeze,
+        EscrowState, GlobalConfig,
+    },
+};
+
+// fraud cases, the escrow can then only be unfrozen by the authority, or force cancelled or
+// force released by another arbiter
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct FreezeEscrow<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+        constraint = arbiter_registry.is_eligible(
+            &arbiter.key(),
+            global_config.min_arbiter_stake,
+        ) @ P2pError::UnauthorizedArbiter,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = !escrow.is_frozen() @ P2pError::EscrowFrozen,
+        constraint = arbiter.key() != escrow.buyer && arbiter.key() != escrow.seller
+            @ P2pError::UnauthorizedArbiter,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // required when the escrow is frozen during a dispute, its deadlines stop running
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+}
+
+impl<'info> FreezeEscrow<'info> {
+    pub fn freeze_escrow(&mut self, _escrow_id: u64, reason: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // blocks release, cancel and dispute progression, whatever the state
+        // (disputed_by is kept, the dispute deposits are refunded when forced)
+        self.escrow.freeze = Some(EscrowFreeze {
+            arbiter: self.arbiter.key(),
+            prior_state: self.escrow.state.clone(),
+        });
+        self.escrow.state = EscrowState::Frozen(now);
+
+        if self.escrow.disputed_by != EscrowDisputedBy::Nobody {
+            let Some(dispute) = self.dispute.as_mut() else {
+                return Err(P2pError::InvalidEscrowState.into());
+            };
+
+            dispute.frozen_at = now;
+        }
+
+        // emit event
+        emit!(events::EscrowFrozen {
+            id: self.escrow.id,
+            arbiter: self.arbiter.key(),
+            reason,
+            frozen_at: now,
+        });
+
+        Ok(())
+    }
+}
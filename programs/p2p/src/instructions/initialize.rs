@@ -1,8 +1,12 @@
 use anchor_lang::{prelude::*, system_program};
 
 use crate::{
-    constants::{DISPUTE_VAULT_SEED, GLOBAL_CONFIG_SEED},
-    states::GlobalConfig,
+    constants::{
+        DEFAULT_ARBITER_REWARD_BPS, DEFAULT_DISPUTE_RESPONSE_SECS, DEFAULT_MIN_ARBITER_STAKE,
+        DEFAULT_PANEL_QUORUM, DEFAULT_PANEL_SIZE, DEFAULT_PANEL_VOTING_SECS,
+        DEFAULT_RESOLUTION_SLA_SECS, DISPUTE_VAULT_SEED, GLOBAL_CONFIG_SEED,
+    },
+    states::{DisputePolicy, DisputeWindow, GlobalConfig, Ruling},
 };
 
 #[derive(Accounts)]
@@ -43,9 +47,46 @@ impl<'info> Initialize<'info> {
             escrow_count: 0,
             fee_bps,
             fiat_deadline_secs,
-            dispute_deadline_secs,
+            fiat_grace_secs: 0,
+            // disputes from open escrows are disabled by default, no latest dispute time
+            dispute_policy: DisputePolicy {
+                open: DisputeWindow {
+                    enabled: false,
+                    earliest_secs: 0,
+                    latest_secs: 0,
+                },
+                fiat_paid: DisputeWindow {
+                    enabled: true,
+                    earliest_secs: dispute_deadline_secs,
+                    latest_secs: 0,
+                },
+                re_dispute: DisputeWindow {
+                    enabled: true,
+                    earliest_secs: dispute_deadline_secs,
+                    latest_secs: 0,
+                },
+            },
             dispute_fee_escrow,
+            dispute_response_secs: DEFAULT_DISPUTE_RESPONSE_SECS,
             available_lamports: 0,
+            panel_size: DEFAULT_PANEL_SIZE,
+            panel_quorum: DEFAULT_PANEL_QUORUM,
+            panel_voting_secs: DEFAULT_PANEL_VOTING_SECS,
+            min_arbiter_stake: DEFAULT_MIN_ARBITER_STAKE,
+            arbiter_reward_bps: DEFAULT_ARBITER_REWARD_BPS,
+            resolution_sla_secs: DEFAULT_RESOLUTION_SLA_SECS,
+            fallback_arbiter: self.authority.key(),
+            // tokens back to the seller, both deposits returned
+            default_ruling: Ruling {
+                buyer_bps: 0,
+                refund_buyer_deposit: true,
+                refund_seller_deposit: true,
+            },
+            appeal_window_secs: 0,
+            appeal_bond_bps: 0,
+            // verified merchant tier disabled
+            merchant_min_bond: 0,
+            merchant_fee_bps: fee_bps,
             bump: global_config_bump,
         });
 
@@ -1,10 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 use crate::{
-    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED},
+    constants::{DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
     errors::P2pError,
     events,
-    states::{Escrow, EscrowState, GlobalConfig},
+    states::{Escrow, EscrowState, GlobalConfig, MintVault, PaymentProof},
+    utils::post_buyer_bond,
 };
 
 #[derive(Accounts)]
@@ -24,21 +29,98 @@ pub struct MarkEscrowAsPaid<'info> {
         seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
         bump = escrow.bump,
         has_one = buyer,
+        has_one = mint,
         constraint = matches!(escrow.state, EscrowState::Open(_)) @ P2pError::EscrowAlreadyTaken,
+        constraint = !escrow.is_hash_locked() @ P2pError::InvalidEscrowState,
+        constraint = escrow.can_mark_as_paid(global_config.fiat_grace_secs) @ P2pError::FiatDeadlinePassed,
     )]
     pub escrow: Account<'info, Escrow>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    // buyer bond, lamports
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    // buyer bond, escrow mint
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> MarkEscrowAsPaid<'info> {
-    pub fn mark_escrow_as_paid(&mut self, _escrow_id: u64) -> Result<()> {
+    pub fn mark_escrow_as_paid(
+        &mut self,
+        _escrow_id: u64,
+        payment_reference: [u8; 32],
+        receipt_hash: [u8; 32],
+    ) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
 
         self.escrow.state = EscrowState::FiatPaid(now);
+        self.escrow.buyer_acted = true;
+
+        // fixed at the moment of marking, for the seller reconciliation and the arbiters
+        self.escrow.payment_proof = Some(PaymentProof {
+            reference: payment_reference,
+            receipt_hash,
+        });
+
+        // post the buyer bond (returned on release, forfeited if the buyer loses the dispute)
+        if let Some(bond) = self.mint_vault.buyer_bond() {
+            post_buyer_bond(
+                &bond,
+                self.buyer.to_account_info(),
+                Some(self.buyer_ata.to_account_info()),
+                self.dispute_vault.to_account_info(),
+                Some(self.mint_vault_ata.to_account_info()),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+            )?;
+
+            emit!(events::BuyerBondPosted {
+                id: self.escrow.id,
+                buyer: self.buyer.key(),
+                amount: bond.amount,
+                in_lamports: bond.in_lamports,
+            });
+
+            self.escrow.buyer_bond = Some(bond);
+        }
 
         emit!(events::MarkEscrowAsPaid {
             id: self.escrow.id,
             seller: self.escrow.seller,
             buyer: self.buyer.key(),
+            payment_reference,
+            receipt_hash,
             marked_at: now,
         });
 
@@ -1,19 +1,94 @@
+pub mod appeal_ruling;
+pub mod assign_arbiter;
+pub mod attested_release;
+pub mod buyer_abandon;
 pub mod cancel_escrow;
+pub mod claim_arbiter_rewards;
+pub mod claim_arbiter_token_rewards;
+pub mod claim_default_judgment;
+pub mod claim_with_preimage;
 pub mod create_dispute;
 pub mod create_escrow;
+pub mod execute_default_ruling;
+pub mod extend_fiat_deadline;
+pub mod finalize_ruling;
+pub mod force_settle;
+pub mod freeze_escrow;
 pub mod initialize;
 pub mod mark_escrow_as_paid;
+pub mod mutual_cancel;
 pub mod release_tokens_in_escrow;
 pub mod resolve_dispute;
+pub mod set_appeal_policy;
+pub mod set_arbiter;
+pub mod set_arbiter_config;
+pub mod set_buyer_bond;
+pub mod set_dispute_bond;
+pub mod set_dispute_policy;
+pub mod set_escrow_limits;
+pub mod set_fiat_grace_period;
+pub mod set_merchant_config;
+pub mod set_panel_config;
+pub mod set_panel_threshold;
+pub mod set_payment_attester;
+pub mod set_resolution_policy;
+pub mod set_signer_set;
+pub mod settle_dispute;
+pub mod slash_arbiter;
+pub mod slash_merchant_bond;
+pub mod stake_arbiter;
+pub mod stake_merchant_bond;
+pub mod submit_evidence;
+pub mod unfreeze_escrow;
+pub mod unstake_arbiter;
+pub mod unstake_merchant_bond;
 pub mod update_global_config;
+pub mod vote_dispute;
 pub mod withdraw_spl;
 
+pub use appeal_ruling::*;
+pub use assign_arbiter::*;
+pub use attested_release::*;
+pub use buyer_abandon::*;
 pub use cancel_escrow::*;
+pub use claim_arbiter_rewards::*;
+pub use claim_arbiter_token_rewards::*;
+pub use claim_default_judgment::*;
+pub use claim_with_preimage::*;
 pub use create_dispute::*;
 pub use create_escrow::*;
+pub use execute_default_ruling::*;
+pub use extend_fiat_deadline::*;
+pub use finalize_ruling::*;
+pub use force_settle::*;
+pub use freeze_escrow::*;
 pub use initialize::*;
 pub use mark_escrow_as_paid::*;
+pub use mutual_cancel::*;
 pub use release_tokens_in_escrow::*;
 pub use resolve_dispute::*;
+pub use set_appeal_policy::*;
+pub use set_arbiter::*;
+pub use set_arbiter_config::*;
+pub use set_buyer_bond::*;
+pub use set_dispute_bond::*;
+pub use set_dispute_policy::*;
+pub use set_escrow_limits::*;
+pub use set_fiat_grace_period::*;
+pub use set_merchant_config::*;
+pub use set_panel_config::*;
+pub use set_panel_threshold::*;
+pub use set_payment_attester::*;
+pub use set_resolution_policy::*;
+pub use set_signer_set::*;
+pub use slash_arbiter::*;
+pub use slash_merchant_bond::*;
+pub use stake_arbiter::*;
+pub use stake_merchant_bond::*;
+pub use submit_evidence::*;
+pub use unfreeze_escrow::*;
+pub use unstake_arbiter::*;
+pub use unstake_merchant_bond::*;
 pub use update_global_config::*;
+pub use vote_dispute::*;
 pub use withdraw_spl::*;
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{
+        DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{Escrow, GlobalConfig, MintVault, UserStats},
+    utils::{
+        settle_buyer_bond, transfer_from_mint_vault, verify_ed25519_signature, BuyerBondAccounts,
+    },
+};
+
+// buyer and seller approve either by signing the transaction
+// or with an off-chain signature of the cancel message
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct MutualCancel<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    // buyer bond, lamports
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = mint,
+        constraint = escrow.can_mutual_cancel() @ P2pError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // buyer bond, escrow mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MutualCancel<'info> {
+    pub fn mutual_cancel(
+        &mut self,
+        _escrow_id: u64,
+        buyer_signature: Option<[u8; 64]>,
+        seller_signature: Option<[u8; 64]>,
+        bumps: &MutualCancelBumps,
+    ) -> Result<()> {
+        // verify approvals
+        let message = Escrow::cancel_message(&self.escrow.key());
+
+        verify_cancel_approval(&self.buyer, buyer_signature, message.as_bytes())?;
+        verify_cancel_approval(&self.seller, seller_signature, message.as_bytes())?;
+
+        // transfer tokens back to seller (amount and deposited fee)
+        let fee = self.escrow.calculate_fee();
+        let total_amount = self.escrow.amount.checked_add(fee).unwrap();
+
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.seller_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            total_amount,
+        )?;
+
+        // return the buyer bond
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: Some(self.seller_ata.to_account_info()),
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump: bumps.dispute_vault,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            false,
+        )?;
+
+        // update the reputation of both parties, the buyer approved the cancel
+        self.escrow.buyer_acted = true;
+
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_cancellation(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
+        // emit event
+        emit!(events::EscrowMutuallyCancelled {
+            id: self.escrow.id,
+            seller: self.seller.key(),
+            buyer: self.buyer.key(),
+            mint: self.mint.key(),
+            returned_amount: total_amount,
+            cancelled_at: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+fn verify_cancel_approval(
+    party: &AccountInfo,
+    signature: Option<[u8; 64]>,
+    message: &[u8],
+) -> Result<()> {
+    if party.is_signer {
+        return Ok(());
+    }
+
+    let signature = signature.ok_or(P2pError::MissingCancelApproval)?;
+
+    verify_ed25519_signature(party.key, &signature, message)
+}
@@ -3,14 +3,42 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
+use solana_keccak_hasher as keccak;
+use solana_sdk_ids::sysvar::instructions as instructions_sysvar;
+use solana_secp256k1_recover::secp256k1_recover;
 
 use crate::{
-    constants::{ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    constants::{
+        DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED, USER_STATS_SEED,
+    },
     errors::P2pError,
     events,
-    states::{Escrow, EscrowState, GlobalConfig, MintVault},
+    states::{Escrow, GlobalConfig, MintVault, ReleaseApprover, SignerApproval, UserStats},
+    utils::{
+        settle_buyer_bond, transfer_from_mint_vault, verify_ed25519_signature, BuyerBondAccounts,
+    },
+    webauthn::verify_passkey_assertion,
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ReleaseApproval {
+    Seller {
+        signature: [u8; 64],
+    },
+    SignerSet {
+        approvals: Vec<SignerApproval>,
+    },
+    Ethereum {
+        signature: [u8; 64],
+        recovery_id: u8,
+    },
+    // the P-256 signature itself is checked by a secp256r1 precompile instruction
+    Passkey {
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    },
+}
+
 #[derive(Accounts)]
 #[instruction(escrow_id: u64)]
 pub struct ReleaseTokensInEscrow<'info> {
@@ -21,11 +49,20 @@ pub struct ReleaseTokensInEscrow<'info> {
     pub seller: SystemAccount<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_CONFIG_SEED],
         bump,
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
+    // buyer bond, lamports
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
     #[account(
         mut,
         close = seller,
@@ -65,50 +102,135 @@ pub struct ReleaseTokensInEscrow<'info> {
     )]
     pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
 
+    /// CHECK: instructions sysvar, required only when the release approver is a passkey
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> ReleaseTokensInEscrow<'info> {
-    pub fn release_tokens_in_escrow(&mut self, _escrow_id: u64, signature: [u8; 64]) -> Result<()> {
-        // verify signature
-        let message = format!("approve_release:{}", self.escrow.key()); // less than 3000 CU (tested manually)
-
-        brine_ed25519::sig_verify(
-            &self.seller.key().to_bytes(),
-            &signature,
-            &message.as_bytes(),
-        )
-        .map_err(|err| {
-            msg!("Signature verification failed {:?}", err);
-            P2pError::SignatureVerificationFailed
-        })?;
+    pub fn release_tokens_in_escrow(
+        &mut self,
+        _escrow_id: u64,
+        approval: ReleaseApproval,
+        bumps: &ReleaseTokensInEscrowBumps,
+    ) -> Result<()> {
+        // verify approval
+        let message = Escrow::release_message(&self.escrow.key());
 
-        // transfer tokens to buyer ata
-        let mint_key = self.mint.key();
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[MINT_VAULT_SEED, mint_key.as_ref(), &[self.mint_vault.bump]]];
+        match (&self.escrow.release_approver, approval) {
+            (ReleaseApprover::Seller, ReleaseApproval::Seller { signature }) => {
+                verify_ed25519_signature(&self.seller.key(), &signature, message.as_bytes())?;
+            }
+            (ReleaseApprover::SignerSet, ReleaseApproval::SignerSet { approvals }) => {
+                // the set copied when the escrow was created
+                let signer_set = self
+                    .escrow
+                    .signer_set
+                    .as_ref()
+                    .ok_or(P2pError::InvalidSignerSet)?;
 
-        let cpi_accounts = anchor_spl::token::Transfer {
-            from: self.mint_vault_ata.to_account_info(),
-            to: self.buyer_ata.to_account_info(),
-            authority: self.mint_vault.to_account_info(),
-        };
+                signer_set.verify_approvals(message.as_bytes(), &approvals)?;
+            }
+            (
+                ReleaseApprover::Ethereum { address },
+                ReleaseApproval::Ethereum {
+                    signature,
+                    recovery_id,
+                },
+            ) => {
+                verify_ethereum_signature(address, message.as_bytes(), &signature, recovery_id)?;
+            }
+            (
+                ReleaseApprover::Passkey {
+                    pubkey,
+                    rp_id_hash,
+                    origin_hash,
+                },
+                ReleaseApproval::Passkey {
+                    authenticator_data,
+                    client_data_json,
+                },
+            ) => {
+                let instructions_sysvar = self
+                    .instructions_sysvar
+                    .as_ref()
+                    .ok_or(P2pError::MissingSecp256r1Verification)?;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
+                verify_passkey_assertion(
+                    instructions_sysvar,
+                    pubkey,
+                    rp_id_hash,
+                    origin_hash,
+                    message.as_bytes(),
+                    &authenticator_data,
+                    &client_data_json,
+                )?;
+            }
+            _ => return Err(P2pError::InvalidReleaseApproval.into()),
+        }
 
-        let fee = self.global_config.calculate_fee(self.escrow.amount);
+        // transfer tokens to buyer ata
+        let fee = self.escrow.calculate_fee();
 
-        anchor_spl::token::transfer(cpi_ctx, self.escrow.amount.checked_sub(fee).unwrap())?;
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.buyer_ata.to_account_info(),
+            self.token_program.to_account_info(),
+            self.escrow.amount.checked_sub(fee).unwrap(),
+        )?;
 
         // update available amount to withdraw in mint vault
         self.mint_vault.add_available_amount(fee);
 
+        // the buyer bond is returned
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: None,
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump: bumps.dispute_vault,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            false,
+        )?;
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_trade(&mut self.buyer_stats, &mut self.seller_stats, &self.escrow);
+
         // emit event
         emit!(events::TokensReleased {
             id: self.escrow.id,
@@ -121,3 +243,36 @@ impl<'info> ReleaseTokensInEscrow<'info> {
         Ok(())
     }
 }
+
+// EIP-191 (`personal_sign`) signature over the release message
+fn verify_ethereum_signature(
+    address: &[u8; 20],
+    message: &[u8],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<()> {
+    // wallets return v = 27 or 28
+    let recovery_id = match recovery_id {
+        0 | 1 => recovery_id,
+        27 | 28 => recovery_id - 27,
+        _ => return Err(P2pError::InvalidRecoveryId.into()),
+    };
+
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    let hash = keccak::hashv(&[prefix.as_bytes(), message]);
+
+    let pubkey = secp256k1_recover(&hash.to_bytes(), recovery_id, signature).map_err(|err| {
+        msg!("Signature recovery failed {:?}", err);
+        P2pError::SignatureVerificationFailed
+    })?;
+
+    // address = last 20 bytes of keccak256(uncompressed pubkey)
+    let recovered = keccak::hash(&pubkey.to_bytes());
+
+    require!(
+        recovered.to_bytes()[12..] == address[..],
+        P2pError::SignatureVerificationFailed
+    );
+
+    Ok(())
+}
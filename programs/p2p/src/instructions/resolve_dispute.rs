@@ -1,143 +1,92 @@
-use anchor_lang::{prelude::*, system_program};
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token_interface::{Mint, TokenAccount, TokenInterface},
-};
+use anchor_lang::prelude::*;
 
-use crate::{
-    constants::{DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
-    events,
-    states::{Escrow, EscrowState, GlobalConfig, MintVault},
-};
+// glob import, the composite accounts derive also needs the generated client modules
+use super::settle_dispute::*;
+
+use crate::{errors::P2pError, events, states::Ruling};
 
 #[derive(Accounts)]
 #[instruction(escrow_id: u64)]
 pub struct ResolveDispute<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    #[account(mut)]
-    pub to: SystemAccount<'info>,
-
-    #[account(
-        mut,
-        seeds = [GLOBAL_CONFIG_SEED],
-        bump = global_config.bump,
-        has_one = authority,
-    )]
-    pub global_config: Account<'info, GlobalConfig>,
-
-    #[account(
-        mut,
-        seeds = [DISPUTE_VAULT_SEED],
-        bump,
-    )]
-    pub dispute_vault: SystemAccount<'info>,
-
     #[account(
-        mut,
-        close = to,
-        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
-        bump = escrow.bump,
-        has_one = mint,
-        constraint = matches!(escrow.state, EscrowState::ReDispute(_)),
-        constraint = to.key() == escrow.buyer || to.key() == escrow.seller,
+        constraint = !settlement.escrow.is_party(&settlement.signer.key()) @ P2pError::PartyCannotArbitrate,
     )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(address = escrow.mint)]
-    pub mint: InterfaceAccount<'info, Mint>,
-
-    #[account(
-        mut,
-        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
-        bump = mint_vault.bump,
-    )]
-    pub mint_vault: Account<'info, MintVault>,
-
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = to,
-        associated_token::token_program = token_program,
-    )]
-    pub to_ata: InterfaceAccount<'info, TokenAccount>,
-
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = mint_vault,
-        associated_token::token_program = token_program,
-    )]
-    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
-
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
+    pub settlement: SettleDispute<'info>,
 }
 
 impl<'info> ResolveDispute<'info> {
-    pub fn resolve_dispute(&mut self, _escrow_id: u64, dispute_vault_bump: u8) -> Result<()> {
-        // transfer SOL from dispute_vault to 'to' account
-        let signer_seeds: &[&[&[u8]]] = &[&[DISPUTE_VAULT_SEED, &[dispute_vault_bump]]];
-
-        let cpi_accounts = system_program::Transfer {
-            from: self.dispute_vault.to_account_info(),
-            to: self.to.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.system_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
+    pub fn resolve_dispute(
+        &mut self,
+        _escrow_id: u64,
+        buyer_bps: u16,
+        refund_buyer_deposit: bool,
+        refund_seller_deposit: bool,
+        bumps: &SettleDisputeBumps,
+    ) -> Result<()> {
+        let arbiter = self.settlement.signer.key();
+        let dispute = &self.settlement.dispute;
+        let global_config = &self.settlement.global_config;
+
+        require!(
+            !dispute.is_awaiting_appeal(),
+            P2pError::RulingAwaitingAppeal
         );
 
-        system_program::transfer(cpi_ctx, self.global_config.dispute_fee_escrow)?;
-
-        // transfer tokens from escrow
-        let mint_key = self.mint.key();
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[MINT_VAULT_SEED, mint_key.as_ref(), &[self.mint_vault.bump]]];
-
-        let cpi_accounts = anchor_spl::token::Transfer {
-            from: self.mint_vault_ata.to_account_info(),
-            to: self.to_ata.to_account_info(),
-            authority: self.mint_vault.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-
-        let fee = self.global_config.calculate_fee(self.escrow.amount);
-
-        let amount = if self.to.key() == self.escrow.buyer {
-            // complete release to buyer (amount - fee)
-            self.escrow.amount.checked_sub(fee).unwrap()
+        if dispute.panel {
+            // panels rule by vote, the fallback arbiter steps in if there is no quorum in time
+            require!(
+                arbiter == global_config.fallback_arbiter
+                    && Clock::get()?.unix_timestamp > dispute.voting_deadline,
+                P2pError::PanelDisputeNotEscalated
+            );
         } else {
-            // refund to seller (full deposit amount)
-            self.escrow.amount.checked_add(fee).unwrap()
-        };
-
-        anchor_spl::token::transfer(cpi_ctx, amount)?;
+            require!(
+                dispute.can_resolve(
+                    &arbiter,
+                    self.settlement
+                        .arbiter_registry
+                        .is_eligible(&arbiter, global_config.min_arbiter_stake),
+                    arbiter == global_config.fallback_arbiter,
+                    Clock::get()?.unix_timestamp,
+                ),
+                P2pError::UnauthorizedArbiter
+            );
+        }
 
-        // update available lamports in global_config
-        self.global_config.add_available_lamports();
+        let ruling = Ruling {
+            buyer_bps,
+            refund_buyer_deposit,
+            refund_seller_deposit,
+        };
 
-        // update available amount in mint_vault if flow is completed (to buyer)
-        if self.to.key() == self.escrow.buyer {
-            self.mint_vault.add_available_amount(fee);
+        // first tier rulings wait for the appeal window (panel rulings are final)
+        if !self.settlement.dispute.panel && self.settlement.global_config.appeal_window_secs > 0 {
+            require!(buyer_bps <= 10_000, P2pError::InvalidSplitBps);
+
+            let appeal_deadline = Clock::get()?
+                .unix_timestamp
+                .checked_add(self.settlement.global_config.appeal_window_secs)
+                .unwrap();
+
+            let dispute = &mut self.settlement.dispute;
+            dispute.pending_ruling = Some(ruling);
+            dispute.arbiter = Some(arbiter);
+            dispute.appeal_deadline = appeal_deadline;
+
+            emit!(events::RulingRecorded {
+                id: dispute.escrow_id,
+                arbiter,
+                buyer_bps,
+                refund_buyer_deposit,
+                refund_seller_deposit,
+                appeal_deadline,
+            });
+
+            return Ok(());
         }
 
-        // emit event
-        emit!(events::DisputeResolved {
-            id: self.escrow.id,
-            winner: self.to.key(),
-            resolved_at: Clock::get()?.unix_timestamp
-        });
-
-        Ok(())
+        // the fallback arbiter is only rewarded if it is also a registered arbiter
+        self.settlement
+            .settle(ruling, Some(arbiter), &[arbiter], bumps)
     }
 }
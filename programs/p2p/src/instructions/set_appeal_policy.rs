@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::GLOBAL_CONFIG_SEED, errors::P2pError, events, states::GlobalConfig};
+
+#[derive(Accounts)]
+pub struct SetAppealPolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetAppealPolicy<'info> {
+    pub fn set_appeal_policy(
+        &mut self,
+        appeal_window_secs: i64,
+        appeal_bond_bps: u16,
+    ) -> Result<()> {
+        // appealing must cost more than the appellant's dispute deposit
+        require!(
+            appeal_window_secs == 0 || appeal_bond_bps > 10_000,
+            P2pError::InvalidAppealConfig
+        );
+
+        self.global_config.appeal_window_secs = appeal_window_secs;
+        self.global_config.appeal_bond_bps = appeal_bond_bps;
+
+        // emit event
+        emit!(events::AppealPolicyUpdated {
+            appeal_window_secs,
+            appeal_bond_bps,
+        });
+
+        Ok(())
+    }
+}
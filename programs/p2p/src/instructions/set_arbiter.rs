@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, GLOBAL_CONFIG_SEED},
+    events,
+    states::{ArbiterRegistry, GlobalConfig},
+};
+
+#[derive(Accounts)]
+pub struct SetArbiter<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ArbiterRegistry::SIZE,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetArbiter<'info> {
+    pub fn set_arbiter(
+        &mut self,
+        arbiter: Pubkey,
+        active: bool,
+        arbiter_registry_bump: u8,
+    ) -> Result<()> {
+        self.arbiter_registry.bump = arbiter_registry_bump;
+        self.arbiter_registry.set_arbiter(arbiter, active)?;
+
+        // emit event
+        emit!(events::ArbiterUpdated { arbiter, active });
+
+        Ok(())
+    }
+}
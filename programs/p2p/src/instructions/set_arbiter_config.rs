@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::GLOBAL_CONFIG_SEED, errors::P2pError, events, states::GlobalConfig};
+
+#[derive(Accounts)]
+pub struct SetArbiterConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetArbiterConfig<'info> {
+    pub fn set_arbiter_config(
+        &mut self,
+        min_arbiter_stake: u64,
+        arbiter_reward_bps: u16,
+    ) -> Result<()> {
+        require!(arbiter_reward_bps <= 10_000, P2pError::InvalidArbiterConfig);
+
+        self.global_config.min_arbiter_stake = min_arbiter_stake;
+        self.global_config.arbiter_reward_bps = arbiter_reward_bps;
+
+        // emit event
+        emit!(events::ArbiterConfigUpdated {
+            min_arbiter_stake,
+            arbiter_reward_bps,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::{GlobalConfig, MintVault},
+};
+
+#[derive(Accounts)]
+pub struct SetBuyerBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintVault::SIZE,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetBuyerBond<'info> {
+    // amount set to 0 disables the buyer bond for this mint
+    pub fn set_buyer_bond(
+        &mut self,
+        amount: u64,
+        in_lamports: bool,
+        cut_bps: u16,
+        mint_vault_bump: u8,
+    ) -> Result<()> {
+        require!(cut_bps <= 10_000, P2pError::InvalidBuyerBondConfig);
+
+        // the mint vault may not exist yet if no escrow was created for this mint
+        self.mint_vault
+            .init_if_needed(self.mint.key(), mint_vault_bump);
+
+        self.mint_vault.buyer_bond = amount;
+        self.mint_vault.buyer_bond_in_lamports = in_lamports;
+        self.mint_vault.buyer_bond_cut_bps = cut_bps;
+
+        // emit event
+        emit!(events::BuyerBondUpdated {
+            mint: self.mint.key(),
+            amount,
+            in_lamports,
+            cut_bps,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::{GlobalConfig, MintVault},
+};
+
+#[derive(Accounts)]
+pub struct SetDisputeBond<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintVault::SIZE,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetDisputeBond<'info> {
+    // bps and floor set to 0 go back to the lamport dispute fee escrow
+    pub fn set_dispute_bond(
+        &mut self,
+        bond_bps: u16,
+        floor: u64,
+        cap: u64,
+        mint_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            bond_bps <= 10_000 && (cap == 0 || cap >= floor),
+            P2pError::InvalidDisputeBondConfig
+        );
+
+        // the mint vault may not exist yet if no escrow was created for this mint
+        self.mint_vault
+            .init_if_needed(self.mint.key(), mint_vault_bump);
+
+        self.mint_vault.dispute_bond_bps = bond_bps;
+        self.mint_vault.dispute_bond_floor = floor;
+        self.mint_vault.dispute_bond_cap = cap;
+
+        // emit event
+        emit!(events::DisputeBondUpdated {
+            mint: self.mint.key(),
+            bond_bps,
+            floor,
+            cap,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::GLOBAL_CONFIG_SEED,
+    events,
+    states::{DisputePolicy, GlobalConfig},
+};
+
+#[derive(Accounts)]
+pub struct SetDisputePolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetDisputePolicy<'info> {
+    pub fn set_dispute_policy(&mut self, dispute_policy: DisputePolicy) -> Result<()> {
+        dispute_policy.validate()?;
+
+        self.global_config.dispute_policy = dispute_policy;
+
+        // emit event
+        emit!(events::DisputePolicyUpdated {
+            dispute_policy: self.global_config.dispute_policy.clone(),
+        });
+
+        Ok(())
+    }
+}
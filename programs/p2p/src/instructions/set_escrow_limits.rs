@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::{GlobalConfig, MintVault},
+};
+
+#[derive(Accounts)]
+pub struct SetEscrowLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintVault::SIZE,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetEscrowLimits<'info> {
+    // the verified merchants limit can only be higher (0 = no limit)
+    pub fn set_escrow_limits(
+        &mut self,
+        escrow_limit: u64,
+        merchant_escrow_limit: u64,
+        mint_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            merchant_escrow_limit == 0
+                || (escrow_limit > 0 && merchant_escrow_limit >= escrow_limit),
+            P2pError::InvalidEscrowLimits
+        );
+
+        // the mint vault may not exist yet if no escrow was created for this mint
+        self.mint_vault
+            .init_if_needed(self.mint.key(), mint_vault_bump);
+
+        self.mint_vault.escrow_limit = escrow_limit;
+        self.mint_vault.merchant_escrow_limit = merchant_escrow_limit;
+
+        // emit event
+        emit!(events::EscrowLimitsUpdated {
+            mint: self.mint.key(),
+            escrow_limit,
+            merchant_escrow_limit,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::GLOBAL_CONFIG_SEED, errors::P2pError, events, states::GlobalConfig};
+
+#[derive(Accounts)]
+pub struct SetFiatGracePeriod<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetFiatGracePeriod<'info> {
+    pub fn set_fiat_grace_period(&mut self, fiat_grace_secs: i64) -> Result<()> {
+        require!(fiat_grace_secs >= 0, P2pError::InvalidFiatGracePeriod);
+
+        self.global_config.fiat_grace_secs = fiat_grace_secs;
+
+        // emit event
+        emit!(events::FiatGracePeriodUpdated { fiat_grace_secs });
+
+        Ok(())
+    }
+}
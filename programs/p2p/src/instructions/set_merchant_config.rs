@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::GLOBAL_CONFIG_SEED, errors::P2pError, events, states::GlobalConfig};
+
+#[derive(Accounts)]
+pub struct SetMerchantConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetMerchantConfig<'info> {
+    // merchant_min_bond set to 0 disables the verified merchant tier
+    pub fn set_merchant_config(
+        &mut self,
+        merchant_min_bond: u64,
+        merchant_fee_bps: u16,
+    ) -> Result<()> {
+        require!(merchant_fee_bps <= 10_000, P2pError::InvalidMerchantConfig);
+
+        self.global_config.merchant_min_bond = merchant_min_bond;
+        self.global_config.merchant_fee_bps = merchant_fee_bps;
+
+        // emit event
+        emit!(events::MerchantConfigUpdated {
+            merchant_min_bond,
+            merchant_fee_bps,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::GLOBAL_CONFIG_SEED, events, states::GlobalConfig};
+
+#[derive(Accounts)]
+pub struct SetPanelConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetPanelConfig<'info> {
+    pub fn set_panel_config(
+        &mut self,
+        panel_size: u8,
+        panel_quorum: u8,
+        panel_voting_secs: i64,
+    ) -> Result<()> {
+        self.global_config.panel_size = panel_size;
+        self.global_config.panel_quorum = panel_quorum;
+        self.global_config.panel_voting_secs = panel_voting_secs;
+
+        self.global_config.validate_panel()?;
+
+        // emit event
+        emit!(events::PanelConfigUpdated {
+            panel_size,
+            panel_quorum,
+            panel_voting_secs,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
+    events,
+    states::{GlobalConfig, MintVault},
+};
+
+#[derive(Accounts)]
+pub struct SetPanelThreshold<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MintVault::SIZE,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPanelThreshold<'info> {
+    pub fn set_panel_threshold(&mut self, panel_threshold: u64, mint_vault_bump: u8) -> Result<()> {
+        // the mint vault may not exist yet if no escrow was created for this mint
+        self.mint_vault
+            .init_if_needed(self.mint.key(), mint_vault_bump);
+
+        self.mint_vault.panel_threshold = panel_threshold;
+
+        // emit event
+        emit!(events::PanelThresholdUpdated {
+            mint: self.mint.key(),
+            panel_threshold,
+        });
+
+        Ok(())
+    }
+}
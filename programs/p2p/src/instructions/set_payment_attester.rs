@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{GLOBAL_CONFIG_SEED, PAYMENT_ATTESTER_SEED},
+    events,
+    states::{GlobalConfig, PaymentAttester},
+};
+
+#[derive(Accounts)]
+#[instruction(attester: Pubkey)]
+pub struct SetPaymentAttester<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PaymentAttester::SIZE,
+        seeds = [PAYMENT_ATTESTER_SEED, attester.as_ref()],
+        bump,
+    )]
+    pub payment_attester: Account<'info, PaymentAttester>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPaymentAttester<'info> {
+    pub fn set_payment_attester(
+        &mut self,
+        attester: Pubkey,
+        active: bool,
+        payment_attester_bump: u8,
+    ) -> Result<()> {
+        self.payment_attester.set_inner(PaymentAttester {
+            attester,
+            active,
+            bump: payment_attester_bump,
+        });
+
+        // emit event
+        emit!(events::PaymentAttesterUpdated { attester, active });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::GLOBAL_CONFIG_SEED,
+    errors::P2pError,
+    events,
+    states::{GlobalConfig, Ruling},
+};
+
+#[derive(Accounts)]
+pub struct SetResolutionPolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+impl<'info> SetResolutionPolicy<'info> {
+    pub fn set_resolution_policy(
+        &mut self,
+        resolution_sla_secs: i64,
+        fallback_arbiter: Pubkey,
+        default_ruling: Ruling,
+    ) -> Result<()> {
+        // a zero SLA would make every re-dispute overdue at once
+        require!(resolution_sla_secs > 0, P2pError::InvalidResolutionPolicy);
+        require!(
+            default_ruling.buyer_bps <= 10_000,
+            P2pError::InvalidSplitBps
+        );
+
+        self.global_config.resolution_sla_secs = resolution_sla_secs;
+        self.global_config.fallback_arbiter = fallback_arbiter;
+        self.global_config.default_ruling = default_ruling;
+
+        // emit event
+        emit!(events::ResolutionPolicyUpdated {
+            resolution_sla_secs,
+            fallback_arbiter,
+            default_ruling,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::SIGNER_SET_SEED, events, states::SignerSet};
+
+#[derive(Accounts)]
+pub struct SetSignerSet<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = SignerSet::SIZE,
+        seeds = [SIGNER_SET_SEED, owner.key().as_ref()],
+        bump,
+    )]
+    pub signer_set: Account<'info, SignerSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetSignerSet<'info> {
+    pub fn set_signer_set(
+        &mut self,
+        threshold: u8,
+        signers: Vec<Pubkey>,
+        signer_set_bump: u8,
+    ) -> Result<()> {
+        SignerSet::validate(threshold, &signers)?;
+
+        self.signer_set.set_inner(SignerSet {
+            owner: self.owner.key(),
+            threshold,
+            signers: signers.clone(),
+            bump: signer_set_bump,
+        });
+
+        // emit event
+        emit!(events::SignerSetUpdated {
+            owner: self.owner.key(),
+            threshold,
+            signers,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{
+        ARBITER_REGISTRY_SEED, DISPUTE_SEED, DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED,
+        MERCHANT_PROFILE_SEED, MINT_VAULT_SEED, USER_STATS_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{
+        ArbiterRegistry, Dispute, DisputeOutcome, Escrow, EscrowState, GlobalConfig,
+        MerchantProfile, MintVault, Ruling, UserStats,
+    },
+    utils::{
+        settle_buyer_bond, transfer_from_dispute_vault, transfer_from_mint_vault,
+        transfer_tokens_from_dispute_vault, BuyerBondAccounts,
+    },
+};
+
+// accounts shared by the instructions that settle a dispute (single arbiter or panel)
+// the escrow is closed manually, a panel vote only settles once the quorum is reached
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct SettleDispute<'info> {
+    // acting arbiter, or anyone executing the default ruling
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        has_one = mint,
+        constraint = matches!(escrow.state, EscrowState::ReDispute(_)) @ P2pError::InvalidEscrowState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [MINT_VAULT_SEED, mint.key().as_ref()],
+        bump = mint_vault.bump,
+    )]
+    pub mint_vault: Account<'info, MintVault>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = mint_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub mint_vault_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // required when the dispute deposits were paid in the escrow mint (created with the dispute)
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = dispute_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub dispute_vault_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = UserStats::SIZE,
+        seeds = [USER_STATS_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_stats: Account<'info, UserStats>,
+
+    /// CHECK: may not exist (the seller never staked a bond), read and written manually
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub merchant_profile: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleDispute<'info> {
+    // splits the escrow, refunds/forfeits the deposits, rewards the arbiters,
+    // records the outcome and closes the escrow
+    pub fn settle(
+        &mut self,
+        ruling: Ruling,
+        arbiter: Option<Pubkey>,
+        rewarded_arbiters: &[Pubkey],
+        bumps: &SettleDisputeBumps,
+    ) -> Result<()> {
+        require!(ruling.buyer_bps <= 10_000, P2pError::InvalidSplitBps);
+
+        // refund the deposits actually made, the non refunded ones are forfeited
+        let mut forfeited_deposits = 0;
+
+        let buyer_deposit_refunded = if ruling.refund_buyer_deposit {
+            self.dispute.buyer_deposit
+        } else {
+            forfeited_deposits += self.dispute.buyer_deposit;
+            0
+        };
+
+        let seller_deposit_refunded = if ruling.refund_seller_deposit {
+            self.dispute.seller_deposit
+        } else {
+            forfeited_deposits += self.dispute.seller_deposit;
+            0
+        };
+
+        if buyer_deposit_refunded > 0 {
+            self.refund_deposit(
+                self.buyer.to_account_info(),
+                self.buyer_ata.to_account_info(),
+                buyer_deposit_refunded,
+                bumps.dispute_vault,
+            )?;
+        }
+
+        if seller_deposit_refunded > 0 {
+            self.refund_deposit(
+                self.seller.to_account_info(),
+                self.seller_ata.to_account_info(),
+                seller_deposit_refunded,
+                bumps.dispute_vault,
+            )?;
+        }
+
+        // the appeal bond (in the deposit mint) is refunded if the ruling was overturned,
+        // forfeited otherwise
+        if let Some(appellant) = self.dispute.appellant {
+            let bond = self.dispute.appeal_bond;
+            let overturned = self.dispute.is_overturned(ruling.buyer_bps);
+
+            if overturned {
+                let (to, to_ata) = if appellant == self.buyer.key() {
+                    (
+                        self.buyer.to_account_info(),
+                        self.buyer_ata.to_account_info(),
+                    )
+                } else {
+                    (
+                        self.seller.to_account_info(),
+                        self.seller_ata.to_account_info(),
+                    )
+                };
+
+                self.refund_deposit(to, to_ata, bond, bumps.dispute_vault)?;
+            } else {
+                forfeited_deposits += bond;
+            }
+
+            emit!(events::AppealSettled {
+                id: self.escrow.id,
+                appellant,
+                bond,
+                overturned,
+            });
+        }
+
+        // forfeited token deposits go to the mint vault like the fees
+        let (forfeited_lamports, forfeited_tokens) = if self.dispute.deposit_mint.is_some() {
+            (0, forfeited_deposits)
+        } else {
+            (forfeited_deposits, 0)
+        };
+
+        if forfeited_tokens > 0 {
+            transfer_tokens_from_dispute_vault(
+                self.dispute_vault.to_account_info(),
+                self.dispute_vault_ata.as_ref(),
+                self.mint_vault_ata.to_account_info(),
+                self.token_program.to_account_info(),
+                forfeited_tokens,
+                bumps.dispute_vault,
+            )?;
+        }
+
+        // split tokens between buyer and seller
+        let (buyer_amount, seller_amount, fee) = self.escrow.calculate_split(ruling.buyer_bps);
+
+        if buyer_amount > 0 {
+            self.transfer_tokens(self.buyer_ata.to_account_info(), buyer_amount)?;
+        }
+
+        if seller_amount > 0 {
+            self.transfer_tokens(self.seller_ata.to_account_info(), seller_amount)?;
+        }
+
+        // the buyer loses the bond only if the whole escrow goes to the seller
+        self.settle_buyer_bond(ruling.buyer_bps == 0, bumps.dispute_vault)?;
+
+        // reward the arbiters with part of the forfeited deposits (kept in the dispute vault,
+        // or in the mint vault ata for the tokens, until claimed), the rest is available for
+        // the authority
+        let arbiter_rewards = self.arbiter_registry.reward_arbiters(
+            rewarded_arbiters,
+            self.global_config
+                .calculate_arbiter_reward(forfeited_lamports),
+        );
+
+        let arbiter_token_rewards = self.mint_vault.reward_arbiters(
+            rewarded_arbiters,
+            self.global_config
+                .calculate_arbiter_reward(forfeited_tokens),
+            &self.arbiter_registry,
+        );
+
+        // update available lamports in global_config
+        self.global_config
+            .add_available_lamports(forfeited_lamports.checked_sub(arbiter_rewards).unwrap());
+
+        // update available amount in mint_vault with the fee of the buyer share
+        self.mint_vault.add_available_amount(
+            fee.checked_add(forfeited_tokens)
+                .unwrap()
+                .checked_sub(arbiter_token_rewards)
+                .unwrap(),
+        );
+
+        // record outcome
+        let now = Clock::get()?.unix_timestamp;
+
+        self.dispute.arbiter = arbiter;
+        self.dispute.outcome = DisputeOutcome::Resolved {
+            buyer_bps: ruling.buyer_bps,
+            buyer_deposit_refunded,
+            seller_deposit_refunded,
+            resolved_at: now,
+        };
+
+        // the merchant bond can be unstaked once its disputes are settled
+        if self.dispute.verified_merchant {
+            MerchantProfile::record_pending_dispute(&self.merchant_profile, false)?;
+        }
+
+        // the resolution SLA was not met
+        if self.dispute.is_overdue(now) {
+            emit!(events::ResolutionDeadlineBreached {
+                id: self.escrow.id,
+                assigned_arbiter: self.dispute.assigned_arbiter,
+                resolution_deadline: self.dispute.resolution_deadline,
+                resolved_by: arbiter,
+                breached_at: now,
+            });
+        }
+
+        // update the reputation of both parties
+        self.buyer_stats
+            .init_if_needed(self.escrow.buyer, bumps.buyer_stats);
+        self.seller_stats
+            .init_if_needed(self.escrow.seller, bumps.seller_stats);
+        UserStats::record_dispute_outcome(
+            &mut self.buyer_stats,
+            &mut self.seller_stats,
+            &self.escrow,
+            ruling.buyer_bps,
+        );
+
+        // close escrow, rent goes back to the seller
+        self.escrow.close(self.seller.to_account_info())?;
+
+        // emit event
+        emit!(events::DisputeResolved {
+            id: self.escrow.id,
+            arbiter,
+            buyer: self.buyer.key(),
+            seller: self.seller.key(),
+            buyer_bps: ruling.buyer_bps,
+            buyer_amount,
+            seller_amount,
+            fee,
+            buyer_deposit_refunded,
+            seller_deposit_refunded,
+            forfeited_lamports,
+            forfeited_tokens,
+            arbiter_rewards,
+            arbiter_token_rewards,
+            resolved_at: now,
+        });
+
+        Ok(())
+    }
+
+    fn settle_buyer_bond(&mut self, forfeited: bool, dispute_vault_bump: u8) -> Result<()> {
+        settle_buyer_bond(
+            &self.escrow,
+            BuyerBondAccounts {
+                buyer: self.buyer.to_account_info(),
+                buyer_ata: self.buyer_ata.to_account_info(),
+                seller: self.seller.to_account_info(),
+                seller_ata: Some(self.seller_ata.to_account_info()),
+                global_config: &mut self.global_config,
+                dispute_vault: self.dispute_vault.to_account_info(),
+                dispute_vault_bump,
+                mint_vault: &mut self.mint_vault,
+                mint_vault_ata: self.mint_vault_ata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            forfeited,
+        )
+    }
+
+    // the deposits are refunded in the mint they were paid in
+    fn refund_deposit(
+        &self,
+        to: AccountInfo<'info>,
+        to_ata: AccountInfo<'info>,
+        amount: u64,
+        dispute_vault_bump: u8,
+    ) -> Result<()> {
+        if self.dispute.deposit_mint.is_some() {
+            transfer_tokens_from_dispute_vault(
+                self.dispute_vault.to_account_info(),
+                self.dispute_vault_ata.as_ref(),
+                to_ata,
+                self.token_program.to_account_info(),
+                amount,
+                dispute_vault_bump,
+            )
+        } else {
+            transfer_from_dispute_vault(
+                self.dispute_vault.to_account_info(),
+                to,
+                self.system_program.to_account_info(),
+                amount,
+                dispute_vault_bump,
+            )
+        }
+    }
+
+    fn transfer_tokens(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            to,
+            self.token_program.to_account_info(),
+            amount,
+        )
+    }
+}
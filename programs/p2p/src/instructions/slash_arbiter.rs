@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, DISPUTE_SEED, GLOBAL_CONFIG_SEED},
+    errors::P2pError,
+    events,
+    states::{ArbiterRegistry, Dispute, GlobalConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct SlashArbiter<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    // the dispute is kept after the escrow is closed
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+}
+
+impl<'info> SlashArbiter<'info> {
+    // once per dispute, only if the arbiter's ruling was overturned or it missed its deadline
+    // slashed lamports stay in the dispute vault and become available for the authority
+    pub fn slash_arbiter(&mut self, _escrow_id: u64, arbiter: Pubkey, lamports: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.dispute.is_arbiter_slashable(&arbiter, now),
+            P2pError::ArbiterNotSlashable
+        );
+
+        let entry = self
+            .arbiter_registry
+            .get_mut(&arbiter)
+            .ok_or(P2pError::ArbiterNotRegistered)?;
+
+        let slashed = lamports.min(entry.stake);
+
+        entry.stake -= slashed;
+
+        let remaining_stake = entry.stake;
+
+        self.dispute.slashed_arbiters.push(arbiter);
+
+        self.global_config.add_available_lamports(slashed);
+
+        // emit event
+        emit!(events::ArbiterSlashed {
+            id: self.dispute.escrow_id,
+            arbiter,
+            lamports: slashed,
+            remaining_stake,
+        });
+
+        Ok(())
+    }
+}
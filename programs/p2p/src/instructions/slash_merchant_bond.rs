@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{
+        ARBITER_REGISTRY_SEED, DISPUTE_SEED, DISPUTE_VAULT_SEED, GLOBAL_CONFIG_SEED,
+        MERCHANT_PROFILE_SEED,
+    },
+    errors::P2pError,
+    events,
+    states::{ArbiterRegistry, Dispute, GlobalConfig, MerchantProfile},
+    utils::transfer_from_dispute_vault,
+};
+
+// the dispute is kept after the escrow is closed, the slash is paid to the buyer
+// only by the arbiter that ruled against the merchant (or a panel member of the majority)
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct SlashMerchantBond<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+        constraint = arbiter_registry.is_eligible(
+            &arbiter.key(),
+            global_config.min_arbiter_stake,
+        ) @ P2pError::UnauthorizedArbiter,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        has_one = buyer,
+        constraint = arbiter.key() != dispute.buyer && arbiter.key() != dispute.seller
+            @ P2pError::UnauthorizedArbiter,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, dispute.seller.as_ref()],
+        bump = merchant_profile.bump,
+    )]
+    pub merchant_profile: Account<'info, MerchantProfile>,
+
+    #[account(mut)]
+    pub buyer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SlashMerchantBond<'info> {
+    // once per dispute, only if the seller lost the whole escrow of a verified escrow
+    pub fn slash_merchant_bond(
+        &mut self,
+        _escrow_id: u64,
+        lamports: u64,
+        dispute_vault_bump: u8,
+    ) -> Result<()> {
+        require!(
+            self.dispute
+                .can_slash_merchant(&self.arbiter.key(), self.merchant_profile.bonded_since),
+            P2pError::MerchantNotSlashable
+        );
+
+        let profile = &mut self.merchant_profile;
+
+        // at most the escrow amount (in its mint units, there is no price to convert it)
+        let slashed = lamports.min(profile.bond).min(self.dispute.escrow_amount);
+
+        profile.bond -= slashed;
+        profile.slashed = profile.slashed.checked_add(slashed).unwrap();
+
+        let remaining_bond = profile.bond;
+
+        self.dispute.merchant_slashed = slashed;
+
+        transfer_from_dispute_vault(
+            self.dispute_vault.to_account_info(),
+            self.buyer.to_account_info(),
+            self.system_program.to_account_info(),
+            slashed,
+            dispute_vault_bump,
+        )?;
+
+        // emit event
+        emit!(events::MerchantBondSlashed {
+            id: self.dispute.escrow_id,
+            merchant: self.dispute.seller,
+            buyer: self.buyer.key(),
+            arbiter: self.arbiter.key(),
+            lamports: slashed,
+            remaining_bond,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, DISPUTE_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::ArbiterRegistry,
+};
+
+#[derive(Accounts)]
+pub struct StakeArbiter<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeArbiter<'info> {
+    pub fn stake_arbiter(&mut self, lamports: u64) -> Result<()> {
+        let entry = self
+            .arbiter_registry
+            .get_mut(&self.arbiter.key())
+            .ok_or(P2pError::ArbiterNotRegistered)?;
+
+        require!(entry.unbonding_until == 0, P2pError::ArbiterUnbonding);
+
+        entry.stake = entry.stake.checked_add(lamports).unwrap();
+
+        let stake = entry.stake;
+
+        // stake is held in the dispute vault
+        let cpi_accounts = system_program::Transfer {
+            from: self.arbiter.to_account_info(),
+            to: self.dispute_vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        system_program::transfer(cpi_ctx, lamports)?;
+
+        // emit event
+        emit!(events::ArbiterStakeUpdated {
+            arbiter: self.arbiter.key(),
+            stake,
+            unbonding_until: 0,
+        });
+
+        Ok(())
+    }
+}
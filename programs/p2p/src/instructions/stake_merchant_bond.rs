@@ -0,0 +1,78 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    constants::{DISPUTE_VAULT_SEED, GLOBAL_CONFIG_SEED, MERCHANT_PROFILE_SEED},
+    errors::P2pError,
+    events,
+    states::{GlobalConfig, MerchantProfile},
+};
+
+#[derive(Accounts)]
+pub struct StakeMerchantBond<'info> {
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = merchant,
+        space = MerchantProfile::SIZE,
+        seeds = [MERCHANT_PROFILE_SEED, merchant.key().as_ref()],
+        bump,
+    )]
+    pub merchant_profile: Account<'info, MerchantProfile>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeMerchantBond<'info> {
+    pub fn stake_merchant_bond(&mut self, lamports: u64, merchant_profile_bump: u8) -> Result<()> {
+        let profile = &mut self.merchant_profile;
+
+        require!(profile.unbonding_until == 0, P2pError::MerchantUnbonding);
+
+        // a new bond is not slashable for the disputes resolved before it
+        if profile.bond == 0 {
+            profile.bonded_since = Clock::get()?.unix_timestamp;
+        }
+
+        profile.merchant = self.merchant.key();
+        profile.bond = profile.bond.checked_add(lamports).unwrap();
+        profile.bump = merchant_profile_bump;
+
+        let bond = profile.bond;
+        let verified = profile.is_verified(self.global_config.merchant_min_bond);
+
+        // bond is held in the dispute vault
+        let cpi_accounts = system_program::Transfer {
+            from: self.merchant.to_account_info(),
+            to: self.dispute_vault.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+
+        system_program::transfer(cpi_ctx, lamports)?;
+
+        // emit event
+        emit!(events::MerchantBondUpdated {
+            merchant: self.merchant.key(),
+            bond,
+            unbonding_until: 0,
+            verified,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISPUTE_EVIDENCE_SEED, ESCROW_SEED, MAX_EVIDENCE_ENTRIES, MAX_EVIDENCE_URI_LEN},
+    errors::P2pError,
+    events,
+    states::{DisputeEvidence, Escrow, EvidenceEntry},
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64, content_hash: [u8; 32], uri: String)]
+pub struct SubmitEvidence<'info> {
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    #[account(
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.is_in_dispute() @ P2pError::InvalidEscrowState,
+        constraint = submitter.key() == escrow.buyer || submitter.key() == escrow.seller
+            @ P2pError::UnauthorizedEvidenceSubmitter,
+        // checked before the evidence account is reallocated
+        constraint = uri.len() <= MAX_EVIDENCE_URI_LEN as usize @ P2pError::EvidenceUriTooLong,
+        constraint = dispute_evidence.entries_by(&submitter.key()) < MAX_EVIDENCE_ENTRIES as usize
+            @ P2pError::TooManyEvidenceEntries,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_EVIDENCE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute_evidence.bump,
+        realloc = dispute_evidence.to_account_info().data_len() + DisputeEvidence::entry_space(&uri),
+        realloc::payer = submitter,
+        realloc::zero = false,
+    )]
+    pub dispute_evidence: Account<'info, DisputeEvidence>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitEvidence<'info> {
+    pub fn submit_evidence(
+        &mut self,
+        _escrow_id: u64,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let index = self.dispute_evidence.entries.len() as u8;
+
+        self.dispute_evidence.entries.push(EvidenceEntry {
+            submitted_by: self.submitter.key(),
+            content_hash,
+            uri: uri.clone(),
+            submitted_at: now,
+        });
+
+        // emit event
+        emit!(events::EvidenceSubmitted {
+            id: self.escrow.id,
+            submitted_by: self.submitter.key(),
+            index,
+            content_hash,
+            uri,
+            submitted_at: now,
+        });
+
+        Ok(())
+    }
+}
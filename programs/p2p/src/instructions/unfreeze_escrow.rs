@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISPUTE_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED},
+    errors::P2pError,
+    events,
+    states::{Dispute, DisputeOutcome, Escrow, EscrowDisputedBy, GlobalConfig},
+};
+
+// the escrow goes back to the state it was frozen in, its deadlines (and the dispute's) are
+// pushed back by the time frozen
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct UnfreezeEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        has_one = authority,
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.is_frozen() @ P2pError::EscrowNotFrozen,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // required when the escrow was frozen during a dispute
+    #[account(
+        mut,
+        seeds = [DISPUTE_SEED, escrow_id.to_le_bytes().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.outcome == DisputeOutcome::Pending @ P2pError::InvalidEscrowState,
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+}
+
+impl<'info> UnfreezeEscrow<'info> {
+    pub fn unfreeze_escrow(&mut self, _escrow_id: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let frozen_secs = self.escrow.unfreeze(now)?;
+
+        if self.escrow.disputed_by != EscrowDisputedBy::Nobody {
+            let Some(dispute) = self.dispute.as_mut() else {
+                return Err(P2pError::InvalidEscrowState.into());
+            };
+
+            dispute.unfreeze(frozen_secs);
+        }
+
+        // emit event
+        emit!(events::EscrowUnfrozen {
+            id: self.escrow.id,
+            authority: self.authority.key(),
+            frozen_secs,
+            unfrozen_at: now,
+        });
+
+        Ok(())
+    }
+}
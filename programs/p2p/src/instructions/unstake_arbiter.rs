@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_REGISTRY_SEED, ARBITER_UNBONDING_SECS, DISPUTE_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::ArbiterRegistry,
+    utils::transfer_from_dispute_vault,
+};
+
+#[derive(Accounts)]
+pub struct UnstakeArbiter<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_REGISTRY_SEED],
+        bump = arbiter_registry.bump,
+    )]
+    pub arbiter_registry: Account<'info, ArbiterRegistry>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeArbiter<'info> {
+    // first call starts the unbonding (the arbiter is no longer eligible),
+    // the second one withdraws the whole stake once the unbonding period is over
+    pub fn unstake_arbiter(&mut self, dispute_vault_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let entry = self
+            .arbiter_registry
+            .get_mut(&self.arbiter.key())
+            .ok_or(P2pError::ArbiterNotRegistered)?;
+
+        if entry.unbonding_until == 0 {
+            entry.unbonding_until = now.checked_add(ARBITER_UNBONDING_SECS).unwrap();
+
+            emit!(events::ArbiterStakeUpdated {
+                arbiter: self.arbiter.key(),
+                stake: entry.stake,
+                unbonding_until: entry.unbonding_until,
+            });
+
+            return Ok(());
+        }
+
+        require!(now >= entry.unbonding_until, P2pError::UnbondingNotFinished);
+
+        let lamports = entry.stake;
+
+        entry.stake = 0;
+        entry.unbonding_until = 0;
+
+        // transfer the stake back from the dispute vault
+        transfer_from_dispute_vault(
+            self.dispute_vault.to_account_info(),
+            self.arbiter.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
+            dispute_vault_bump,
+        )?;
+
+        // emit event
+        emit!(events::ArbiterStakeUpdated {
+            arbiter: self.arbiter.key(),
+            stake: 0,
+            unbonding_until: 0,
+        });
+
+        Ok(())
+    }
+}
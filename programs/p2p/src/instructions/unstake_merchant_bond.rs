@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISPUTE_VAULT_SEED, MERCHANT_PROFILE_SEED, MERCHANT_UNBONDING_SECS},
+    errors::P2pError,
+    events,
+    states::MerchantProfile,
+    utils::transfer_from_dispute_vault,
+};
+
+#[derive(Accounts)]
+pub struct UnstakeMerchantBond<'info> {
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MERCHANT_PROFILE_SEED, merchant.key().as_ref()],
+        bump = merchant_profile.bump,
+    )]
+    pub merchant_profile: Account<'info, MerchantProfile>,
+
+    #[account(
+        mut,
+        seeds = [DISPUTE_VAULT_SEED],
+        bump,
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeMerchantBond<'info> {
+    // first call starts the unbonding (the merchant is no longer verified, the bond is still
+    // slashable), the second one withdraws the whole bond once the unbonding period is over,
+    // neither while a dispute of a verified escrow is pending
+    pub fn unstake_merchant_bond(&mut self, dispute_vault_bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let profile = &mut self.merchant_profile;
+
+        // the bond stays slashable until the disputes of the verified escrows are settled
+        require!(
+            profile.pending_disputes == 0,
+            P2pError::MerchantDisputePending
+        );
+
+        if profile.unbonding_until == 0 {
+            profile.unbonding_until = now.checked_add(MERCHANT_UNBONDING_SECS).unwrap();
+
+            emit!(events::MerchantBondUpdated {
+                merchant: self.merchant.key(),
+                bond: profile.bond,
+                unbonding_until: profile.unbonding_until,
+                verified: false,
+            });
+
+            return Ok(());
+        }
+
+        require!(
+            now >= profile.unbonding_until,
+            P2pError::UnbondingNotFinished
+        );
+
+        let lamports = profile.bond;
+
+        profile.bond = 0;
+        profile.unbonding_until = 0;
+
+        // transfer the bond back from the dispute vault
+        transfer_from_dispute_vault(
+            self.dispute_vault.to_account_info(),
+            self.merchant.to_account_info(),
+            self.system_program.to_account_info(),
+            lamports,
+            dispute_vault_bump,
+        )?;
+
+        // emit event
+        emit!(events::MerchantBondUpdated {
+            merchant: self.merchant.key(),
+            bond: 0,
+            unbonding_until: 0,
+            verified: false,
+        });
+
+        Ok(())
+    }
+}
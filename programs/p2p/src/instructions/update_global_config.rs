@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::GLOBAL_CONFIG_SEED, states::GlobalConfig};
+use crate::{constants::GLOBAL_CONFIG_SEED, errors::P2pError, states::GlobalConfig};
 
 #[derive(Accounts)]
 pub struct UpdateGlobalConfig<'info> {
@@ -22,8 +22,8 @@ impl<'info> UpdateGlobalConfig<'info> {
         authority: Option<Pubkey>,
         fee_bps: Option<u16>,
         fiat_deadline_secs: Option<i64>,
-        dispute_deadline_secs: Option<i64>,
         dispute_fee_escrow: Option<u64>,
+        dispute_response_secs: Option<i64>,
     ) -> Result<()> {
         if let Some(authority) = authority {
             self.global_config.authority = authority;
@@ -34,12 +34,16 @@ impl<'info> UpdateGlobalConfig<'info> {
         if let Some(fiat_deadline_secs) = fiat_deadline_secs {
             self.global_config.fiat_deadline_secs = fiat_deadline_secs;
         }
-        if let Some(dispute_deadline_secs) = dispute_deadline_secs {
-            self.global_config.dispute_deadline_secs = dispute_deadline_secs;
-        }
         if let Some(dispute_fee_escrow) = dispute_fee_escrow {
             self.global_config.dispute_fee_escrow = dispute_fee_escrow;
         }
+        if let Some(dispute_response_secs) = dispute_response_secs {
+            require!(
+                dispute_response_secs >= 0,
+                P2pError::InvalidDisputeResponsePeriod
+            );
+            self.global_config.dispute_response_secs = dispute_response_secs;
+        }
 
         Ok(())
     }
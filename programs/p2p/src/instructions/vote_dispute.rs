@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+// glob import, the composite accounts derive also needs the generated client modules
+use super::settle_dispute::*;
+
+use crate::{
+    errors::P2pError,
+    events,
+    states::{PanelVote, Ruling},
+};
+
+#[derive(Accounts)]
+#[instruction(escrow_id: u64)]
+pub struct VoteDispute<'info> {
+    #[account(
+        constraint = !settlement.escrow.is_party(&settlement.signer.key()) @ P2pError::PartyCannotArbitrate,
+        constraint = settlement.arbiter_registry.is_eligible(
+            &settlement.signer.key(),
+            settlement.global_config.min_arbiter_stake,
+        ) @ P2pError::UnauthorizedArbiter,
+    )]
+    pub settlement: SettleDispute<'info>,
+}
+
+impl<'info> VoteDispute<'info> {
+    pub fn vote_dispute(
+        &mut self,
+        _escrow_id: u64,
+        buyer_bps: u16,
+        refund_buyer_deposit: bool,
+        refund_seller_deposit: bool,
+        bumps: &SettleDisputeBumps,
+    ) -> Result<()> {
+        require!(buyer_bps <= 10_000, P2pError::InvalidSplitBps);
+
+        let arbiter = self.settlement.signer.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let dispute = &mut self.settlement.dispute;
+
+        require!(dispute.panel, P2pError::NotPanelDispute);
+        require!(now <= dispute.voting_deadline, P2pError::PanelVotingClosed);
+        // only the members drawn for this dispute (never the parties, nor the appealed arbiter)
+        require!(dispute.is_panel_member(&arbiter), P2pError::NotPanelMember);
+        require!(!dispute.has_voted(&arbiter), P2pError::AlreadyVoted);
+        require!(
+            dispute.votes.len() < dispute.panel_size as usize,
+            P2pError::PanelFull
+        );
+
+        let ruling = Ruling {
+            buyer_bps,
+            refund_buyer_deposit,
+            refund_seller_deposit,
+        };
+
+        dispute.votes.push(PanelVote { arbiter, ruling });
+
+        let votes = dispute.count_votes(&ruling);
+
+        // emit event
+        emit!(events::DisputeVoteCast {
+            id: dispute.escrow_id,
+            arbiter,
+            buyer_bps,
+            refund_buyer_deposit,
+            refund_seller_deposit,
+            votes,
+            voted_at: now,
+        });
+
+        if votes < dispute.panel_quorum {
+            return Ok(());
+        }
+
+        // quorum reached, the arbiters of the majority share the reward
+        let majority: Vec<Pubkey> = dispute
+            .votes
+            .iter()
+            .filter(|vote| vote.ruling == ruling)
+            .map(|vote| vote.arbiter)
+            .collect();
+
+        self.settlement
+            .settle(ruling, Some(arbiter), &majority, bumps)
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
     constants::{GLOBAL_CONFIG_SEED, MINT_VAULT_SEED},
     errors::P2pError,
     states::{GlobalConfig, MintVault},
+    utils::transfer_from_mint_vault,
 };
 
 #[derive(Accounts)]
@@ -57,23 +58,13 @@ pub struct WithdrawSpl<'info> {
 impl<'info> WithdrawSpl<'info> {
     pub fn withdraw_spl(&mut self) -> Result<()> {
         // transfer tokens to authority ata
-        let mint_key = self.mint.key();
-        let signer_seeds: &[&[&[u8]]] =
-            &[&[MINT_VAULT_SEED, mint_key.as_ref(), &[self.mint_vault.bump]]];
-
-        let cpi_accounts = anchor_spl::token::Transfer {
-            from: self.mint_vault_ata.to_account_info(),
-            to: self.authority_ata.to_account_info(),
-            authority: self.mint_vault.to_account_info(),
-        };
-
-        let ctx_cpi = CpiContext::new_with_signer(
+        transfer_from_mint_vault(
+            &self.mint_vault,
+            self.mint_vault_ata.to_account_info(),
+            self.authority_ata.to_account_info(),
             self.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-
-        anchor_spl::token::transfer(ctx_cpi, self.mint_vault.available_amount)?;
+            self.mint_vault.available_amount,
+        )?;
 
         // reset available amount to withdraw in mint vault
         self.mint_vault.available_amount = 0;
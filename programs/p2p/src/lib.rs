@@ -3,10 +3,13 @@ mod errors;
 mod events;
 mod instructions;
 mod states;
+mod utils;
+mod webauthn;
 
 use anchor_lang::prelude::*;
 
 use instructions::*;
+use states::*;
 
 declare_id!("GQKqoMVW3BuSzFRRkfeVsLPArAkRiZkd1vkVNGeqRmJG");
 
@@ -30,33 +33,337 @@ pub mod p2p {
         )
     }
 
-    pub fn create_escrow(ctx: Context<CreateEscrow>, amount: u64) -> Result<()> {
-        ctx.accounts.create_escrow(amount, &ctx.bumps)
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        amount: u64,
+        release_approver: ReleaseApprover,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_escrow(amount, release_approver, &ctx.bumps)
     }
 
-    pub fn mark_escrow_as_paid(ctx: Context<MarkEscrowAsPaid>, escrow_id: u64) -> Result<()> {
-        ctx.accounts.mark_escrow_as_paid(escrow_id)
+    pub fn mark_escrow_as_paid(
+        ctx: Context<MarkEscrowAsPaid>,
+        escrow_id: u64,
+        payment_reference: [u8; 32],
+        receipt_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .mark_escrow_as_paid(escrow_id, payment_reference, receipt_hash)
     }
 
     pub fn release_tokens_in_escrow(
         ctx: Context<ReleaseTokensInEscrow>,
         escrow_id: u64,
+        approval: ReleaseApproval,
+    ) -> Result<()> {
+        ctx.accounts
+            .release_tokens_in_escrow(escrow_id, approval, &ctx.bumps)
+    }
+
+    pub fn extend_fiat_deadline(
+        ctx: Context<ExtendFiatDeadline>,
+        escrow_id: u64,
+        extension_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts.extend_fiat_deadline(escrow_id, extension_secs)
+    }
+
+    pub fn buyer_abandon(ctx: Context<BuyerAbandon>, escrow_id: u64) -> Result<()> {
+        ctx.accounts.buyer_abandon(escrow_id, &ctx.bumps)
+    }
+
+    pub fn mutual_cancel(
+        ctx: Context<MutualCancel>,
+        escrow_id: u64,
+        buyer_signature: Option<[u8; 64]>,
+        seller_signature: Option<[u8; 64]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .mutual_cancel(escrow_id, buyer_signature, seller_signature, &ctx.bumps)
+    }
+
+    pub fn claim_with_preimage(
+        ctx: Context<ClaimWithPreimage>,
+        escrow_id: u64,
+        preimage: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .claim_with_preimage(escrow_id, preimage, &ctx.bumps)
+    }
+
+    pub fn attested_release(
+        ctx: Context<AttestedRelease>,
+        escrow_id: u64,
+        fiat_amount: u64,
+        reference: [u8; 32],
         signature: [u8; 64],
     ) -> Result<()> {
-        ctx.accounts.release_tokens_in_escrow(escrow_id, signature)
+        ctx.accounts
+            .attested_release(escrow_id, fiat_amount, reference, signature, &ctx.bumps)
     }
 
     pub fn cancel_escrow(ctx: Context<CancelEscrow>, escrow_id: u64) -> Result<()> {
-        ctx.accounts.cancel_escrow(escrow_id)
+        ctx.accounts.cancel_escrow(escrow_id, &ctx.bumps)
     }
 
     pub fn create_dispute(ctx: Context<CreateDispute>, escrow_id: u64) -> Result<()> {
-        ctx.accounts.create_dispute(escrow_id)
+        ctx.accounts.create_dispute(escrow_id, &ctx.bumps)
     }
 
-    pub fn resolve_dispute(ctx: Context<ResolveDispute>, escrow_id: u64) -> Result<()> {
+    pub fn assign_arbiter(ctx: Context<AssignArbiter>, escrow_id: u64) -> Result<()> {
+        ctx.accounts.assign_arbiter(escrow_id)
+    }
+
+    pub fn claim_default_judgment(
+        ctx: Context<ClaimDefaultJudgment>,
+        escrow_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.claim_default_judgment(escrow_id, &ctx.bumps)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        escrow_id: u64,
+        buyer_bps: u16,
+        refund_buyer_deposit: bool,
+        refund_seller_deposit: bool,
+    ) -> Result<()> {
+        ctx.accounts.resolve_dispute(
+            escrow_id,
+            buyer_bps,
+            refund_buyer_deposit,
+            refund_seller_deposit,
+            &ctx.bumps.settlement,
+        )
+    }
+
+    pub fn appeal_ruling(ctx: Context<AppealRuling>, escrow_id: u64) -> Result<()> {
+        ctx.accounts.appeal_ruling(escrow_id)
+    }
+
+    pub fn finalize_ruling(ctx: Context<FinalizeRuling>, escrow_id: u64) -> Result<()> {
+        ctx.accounts
+            .finalize_ruling(escrow_id, &ctx.bumps.settlement)
+    }
+
+    pub fn vote_dispute(
+        ctx: Context<VoteDispute>,
+        escrow_id: u64,
+        buyer_bps: u16,
+        refund_buyer_deposit: bool,
+        refund_seller_deposit: bool,
+    ) -> Result<()> {
+        ctx.accounts.vote_dispute(
+            escrow_id,
+            buyer_bps,
+            refund_buyer_deposit,
+            refund_seller_deposit,
+            &ctx.bumps.settlement,
+        )
+    }
+
+    pub fn freeze_escrow(ctx: Context<FreezeEscrow>, escrow_id: u64, reason: u8) -> Result<()> {
+        ctx.accounts.freeze_escrow(escrow_id, reason)
+    }
+
+    pub fn unfreeze_escrow(ctx: Context<UnfreezeEscrow>, escrow_id: u64) -> Result<()> {
+        ctx.accounts.unfreeze_escrow(escrow_id)
+    }
+
+    pub fn force_cancel(
+        ctx: Context<ForceSettle>,
+        escrow_id: u64,
+        reason: u8,
+        forfeit_buyer_bond: bool,
+    ) -> Result<()> {
         ctx.accounts
-            .resolve_dispute(escrow_id, ctx.bumps.dispute_vault)
+            .force_cancel(escrow_id, reason, forfeit_buyer_bond, &ctx.bumps)
+    }
+
+    pub fn force_release(ctx: Context<ForceSettle>, escrow_id: u64, reason: u8) -> Result<()> {
+        ctx.accounts.force_release(escrow_id, reason, &ctx.bumps)
+    }
+
+    pub fn set_arbiter(ctx: Context<SetArbiter>, arbiter: Pubkey, active: bool) -> Result<()> {
+        ctx.accounts
+            .set_arbiter(arbiter, active, ctx.bumps.arbiter_registry)
+    }
+
+    pub fn set_payment_attester(
+        ctx: Context<SetPaymentAttester>,
+        attester: Pubkey,
+        active: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_payment_attester(attester, active, ctx.bumps.payment_attester)
+    }
+
+    pub fn execute_default_ruling(
+        ctx: Context<ExecuteDefaultRuling>,
+        escrow_id: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .execute_default_ruling(escrow_id, &ctx.bumps.settlement)
+    }
+
+    pub fn set_resolution_policy(
+        ctx: Context<SetResolutionPolicy>,
+        resolution_sla_secs: i64,
+        fallback_arbiter: Pubkey,
+        default_ruling: Ruling,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_resolution_policy(resolution_sla_secs, fallback_arbiter, default_ruling)
+    }
+
+    pub fn set_appeal_policy(
+        ctx: Context<SetAppealPolicy>,
+        appeal_window_secs: i64,
+        appeal_bond_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_appeal_policy(appeal_window_secs, appeal_bond_bps)
+    }
+
+    pub fn set_arbiter_config(
+        ctx: Context<SetArbiterConfig>,
+        min_arbiter_stake: u64,
+        arbiter_reward_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_arbiter_config(min_arbiter_stake, arbiter_reward_bps)
+    }
+
+    pub fn stake_arbiter(ctx: Context<StakeArbiter>, lamports: u64) -> Result<()> {
+        ctx.accounts.stake_arbiter(lamports)
+    }
+
+    pub fn unstake_arbiter(ctx: Context<UnstakeArbiter>) -> Result<()> {
+        ctx.accounts.unstake_arbiter(ctx.bumps.dispute_vault)
+    }
+
+    pub fn claim_arbiter_rewards(ctx: Context<ClaimArbiterRewards>) -> Result<()> {
+        ctx.accounts.claim_arbiter_rewards(ctx.bumps.dispute_vault)
+    }
+
+    pub fn claim_arbiter_token_rewards(ctx: Context<ClaimArbiterTokenRewards>) -> Result<()> {
+        ctx.accounts.claim_arbiter_token_rewards()
+    }
+
+    pub fn slash_arbiter(
+        ctx: Context<SlashArbiter>,
+        escrow_id: u64,
+        arbiter: Pubkey,
+        lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts.slash_arbiter(escrow_id, arbiter, lamports)
+    }
+
+    pub fn set_merchant_config(
+        ctx: Context<SetMerchantConfig>,
+        merchant_min_bond: u64,
+        merchant_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_merchant_config(merchant_min_bond, merchant_fee_bps)
+    }
+
+    pub fn stake_merchant_bond(ctx: Context<StakeMerchantBond>, lamports: u64) -> Result<()> {
+        ctx.accounts
+            .stake_merchant_bond(lamports, ctx.bumps.merchant_profile)
+    }
+
+    pub fn unstake_merchant_bond(ctx: Context<UnstakeMerchantBond>) -> Result<()> {
+        ctx.accounts.unstake_merchant_bond(ctx.bumps.dispute_vault)
+    }
+
+    pub fn slash_merchant_bond(
+        ctx: Context<SlashMerchantBond>,
+        escrow_id: u64,
+        lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .slash_merchant_bond(escrow_id, lamports, ctx.bumps.dispute_vault)
+    }
+
+    pub fn set_escrow_limits(
+        ctx: Context<SetEscrowLimits>,
+        escrow_limit: u64,
+        merchant_escrow_limit: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_escrow_limits(escrow_limit, merchant_escrow_limit, ctx.bumps.mint_vault)
+    }
+
+    pub fn set_panel_config(
+        ctx: Context<SetPanelConfig>,
+        panel_size: u8,
+        panel_quorum: u8,
+        panel_voting_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_panel_config(panel_size, panel_quorum, panel_voting_secs)
+    }
+
+    pub fn set_panel_threshold(
+        ctx: Context<SetPanelThreshold>,
+        panel_threshold: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_panel_threshold(panel_threshold, ctx.bumps.mint_vault)
+    }
+
+    pub fn set_buyer_bond(
+        ctx: Context<SetBuyerBond>,
+        amount: u64,
+        in_lamports: bool,
+        cut_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_buyer_bond(amount, in_lamports, cut_bps, ctx.bumps.mint_vault)
+    }
+
+    pub fn set_dispute_bond(
+        ctx: Context<SetDisputeBond>,
+        bond_bps: u16,
+        floor: u64,
+        cap: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_dispute_bond(bond_bps, floor, cap, ctx.bumps.mint_vault)
+    }
+
+    pub fn set_dispute_policy(
+        ctx: Context<SetDisputePolicy>,
+        dispute_policy: DisputePolicy,
+    ) -> Result<()> {
+        ctx.accounts.set_dispute_policy(dispute_policy)
+    }
+
+    pub fn set_fiat_grace_period(
+        ctx: Context<SetFiatGracePeriod>,
+        fiat_grace_secs: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_fiat_grace_period(fiat_grace_secs)
+    }
+
+    pub fn set_signer_set(
+        ctx: Context<SetSignerSet>,
+        threshold: u8,
+        signers: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_signer_set(threshold, signers, ctx.bumps.signer_set)
+    }
+
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        escrow_id: u64,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.submit_evidence(escrow_id, content_hash, uri)
     }
 
     pub fn withdraw_spl(ctx: Context<WithdrawSpl>) -> Result<()> {
@@ -68,15 +375,15 @@ pub mod p2p {
         authority: Option<Pubkey>,
         fee_bps: Option<u16>,
         fiat_deadline_secs: Option<i64>,
-        dispute_deadline_secs: Option<i64>,
         dispute_fee_escrow: Option<u64>,
+        dispute_response_secs: Option<i64>,
     ) -> Result<()> {
         ctx.accounts.update_global_config(
             authority,
             fee_bps,
             fiat_deadline_secs,
-            dispute_deadline_secs,
             dispute_fee_escrow,
+            dispute_response_secs,
         )
     }
 }
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_ARBITERS},
+    errors::P2pError,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct ArbiterRegistry {
+    #[max_len(MAX_ARBITERS)]
+    pub arbiters: Vec<Arbiter>,
+    pub bump: u8,
+}
+
+impl ArbiterRegistry {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + ArbiterRegistry::INIT_SPACE;
+
+    pub fn get(&self, key: &Pubkey) -> Option<&Arbiter> {
+        self.arbiters.iter().find(|arbiter| arbiter.key == *key)
+    }
+
+    pub fn get_mut(&mut self, key: &Pubkey) -> Option<&mut Arbiter> {
+        self.arbiters.iter_mut().find(|arbiter| arbiter.key == *key)
+    }
+
+    // active, enough stake and not unbonding
+    pub fn is_eligible(&self, key: &Pubkey, min_stake: u64) -> bool {
+        self.get(key)
+            .is_some_and(|arbiter| arbiter.is_eligible(min_stake))
+    }
+
+    // adds the arbiter if it's not registered yet
+    pub fn set_arbiter(&mut self, key: Pubkey, active: bool) -> Result<()> {
+        if let Some(arbiter) = self.get_mut(&key) {
+            arbiter.active = active;
+            return Ok(());
+        }
+
+        require!(
+            self.arbiters.len() < MAX_ARBITERS as usize,
+            P2pError::ArbiterRegistryFull
+        );
+
+        self.arbiters.push(Arbiter {
+            key,
+            active,
+            resolved_count: 0,
+            stake: 0,
+            rewards: 0,
+            unbonding_until: 0,
+        });
+
+        Ok(())
+    }
+
+    // picks one of the active arbiters, skipping the excluded keys (the escrow parties)
+    pub fn pick(&self, seed: u64, min_stake: u64, excluded: &[Pubkey]) -> Option<Pubkey> {
+        let candidates: Vec<Pubkey> = self
+            .arbiters
+            .iter()
+            .filter(|arbiter| arbiter.is_eligible(min_stake) && !excluded.contains(&arbiter.key))
+            .map(|arbiter| arbiter.key)
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        Some(candidates[(seed % candidates.len() as u64) as usize])
+    }
+
+    // picks up to `size` distinct active arbiters, skipping the excluded keys
+    pub fn pick_panel(
+        &self,
+        seed: u64,
+        min_stake: u64,
+        size: u8,
+        excluded: &[Pubkey],
+    ) -> Vec<Pubkey> {
+        let mut candidates: Vec<Pubkey> = self
+            .arbiters
+            .iter()
+            .filter(|arbiter| arbiter.is_eligible(min_stake) && !excluded.contains(&arbiter.key))
+            .map(|arbiter| arbiter.key)
+            .collect();
+
+        let size = (size as usize).min(candidates.len());
+        let mut seed = seed;
+
+        // partial Fisher-Yates shuffle, the seed is advanced with an LCG for every seat
+        for i in 0..size {
+            let j = i + (seed % (candidates.len() - i) as u64) as usize;
+            candidates.swap(i, j);
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+        }
+
+        candidates.truncate(size);
+        candidates
+    }
+
+    // records the resolution and splits the reward between the registered arbiters
+    // returns the lamports actually credited (unregistered keys, like the authority, get nothing)
+    pub fn reward_arbiters(&mut self, keys: &[Pubkey], lamports: u64) -> u64 {
+        if keys.is_empty() {
+            return 0;
+        }
+
+        let share = lamports / keys.len() as u64;
+        let mut credited = 0;
+
+        for key in keys {
+            if let Some(arbiter) = self.get_mut(key) {
+                arbiter.resolved_count = arbiter.resolved_count.checked_add(1).unwrap();
+                arbiter.rewards = arbiter.rewards.checked_add(share).unwrap();
+                credited += share;
+            }
+        }
+
+        credited
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Arbiter {
+    pub key: Pubkey,
+    pub active: bool,
+    pub resolved_count: u32,
+    pub stake: u64,           // lamports, held in the dispute vault
+    pub rewards: u64,         // lamports, claimable share of forfeited deposits
+    pub unbonding_until: i64, // 0 = not unbonding
+}
+
+impl Arbiter {
+    pub fn is_eligible(&self, min_stake: u64) -> bool {
+        self.active && self.stake >= min_stake && self.unbonding_until == 0
+    }
+}
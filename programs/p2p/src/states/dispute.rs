@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    ARBITER_DRAW_DELAY_SLOTS, DISCRIMINATOR_SIZE, MAX_PANEL_SIZE, MAX_SLASHED_ARBITERS,
+};
+
+// dispute history of an escrow, kept after the escrow is closed
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub escrow_id: u64,
+    pub escrow: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub escrow_amount: u64,      // caps the merchant slash
+    pub verified_merchant: bool, // the seller's merchant bond backs the dispute
+    pub opened_by: Pubkey,
+    pub opened_at: i64,
+    pub redisputed_at: Option<i64>,
+    pub buyer_deposit: u64,  // lamports, or tokens of the deposit mint
+    pub seller_deposit: u64, // lamports, or tokens of the deposit mint
+    pub deposit_mint: Option<Pubkey>, // set when the deposits are paid in the escrow mint
+    pub assigned_arbiter: Option<Pubkey>, // randomly assigned after re-dispute (single arbiter disputes)
+    pub draw_slot: u64, // future slot whose hash draws the arbiter or the panel, 0 once drawn
+    pub arbiter: Option<Pubkey>, // who resolved the dispute
+    pub outcome: DisputeOutcome,
+    pub panel: bool,      // ruled by a panel of arbiters (high value escrows)
+    pub panel_size: u8,   // panel config when the dispute was opened
+    pub panel_quorum: u8, // matching votes needed, from the same config
+    #[max_len(MAX_PANEL_SIZE)]
+    pub panel_members: Vec<Pubkey>, // randomly drawn once the dispute goes to the panel
+    pub voting_deadline: i64, // panel only, set on re-dispute
+    pub resolution_deadline: i64, // set on re-dispute, then the fallback arbiter and default ruling apply
+    #[max_len(MAX_PANEL_SIZE)]
+    pub votes: Vec<PanelVote>,
+    pub pending_ruling: Option<Ruling>, // first tier ruling, settled once the appeal window is over
+    pub appeal_deadline: i64,
+    pub appellant: Option<Pubkey>, // appealed rulings go to a panel
+    pub appealed_arbiter: Option<Pubkey>, // arbiter of the appealed ruling
+    pub appeal_bond: u64,          // lamports, or tokens of the deposit mint
+    pub merchant_slashed: u64,     // lamports of the seller merchant bond paid to the buyer
+    pub force_settled: bool,       // frozen escrow settled by an arbiter, nobody is at fault
+    pub frozen_at: i64, // set while the escrow is frozen (kept once force settled), 0 otherwise
+    #[max_len(MAX_SLASHED_ARBITERS)]
+    pub slashed_arbiters: Vec<Pubkey>, // slashed once per dispute
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + Dispute::INIT_SPACE;
+
+    pub fn record_deposit(&mut self, party: Pubkey, amount: u64) {
+        if party == self.buyer {
+            self.buyer_deposit = self.buyer_deposit.checked_add(amount).unwrap();
+        } else {
+            self.seller_deposit = self.seller_deposit.checked_add(amount).unwrap();
+        }
+    }
+
+    // deposit recorded for the party, in the deposit mint
+    pub fn deposit_of(&self, party: &Pubkey) -> u64 {
+        if *party == self.buyer {
+            self.buyer_deposit
+        } else {
+            self.seller_deposit
+        }
+    }
+
+    // the arbiters are drawn with the hash of a slot after `now_slot + ARBITER_DRAW_DELAY_SLOTS`
+    pub fn schedule_draw(&mut self, now_slot: u64) {
+        self.assigned_arbiter = None;
+        self.panel_members.clear();
+        self.draw_slot = now_slot.checked_add(ARBITER_DRAW_DELAY_SLOTS).unwrap();
+    }
+
+    pub fn is_draw_pending(&self) -> bool {
+        self.draw_slot > 0
+    }
+
+    // the assigned arbiter (or any active arbiter if none was assigned) can resolve,
+    // the fallback arbiter can too if none was assigned, if it was assigned (expired draw)
+    // or after the resolution deadline (until the draw, only after the resolution deadline)
+    pub fn can_resolve(&self, key: &Pubkey, is_active: bool, is_fallback: bool, now: i64) -> bool {
+        if self.is_draw_pending() {
+            return is_fallback && self.is_overdue(now);
+        }
+
+        match self.assigned_arbiter {
+            Some(assigned) => {
+                (*key == assigned && (is_active || is_fallback))
+                    || (is_fallback && self.is_overdue(now))
+            }
+            None => is_active || is_fallback,
+        }
+    }
+
+    // the whole escrow went to the buyer in a ruling of this arbiter (or of a panel member that
+    // voted for it), not in a force settlement, and the merchant bond was already staked
+    pub fn can_slash_merchant(&self, arbiter: &Pubkey, bonded_since: i64) -> bool {
+        let DisputeOutcome::Resolved {
+            buyer_bps: 10_000,
+            resolved_at,
+            ..
+        } = self.outcome
+        else {
+            return false;
+        };
+
+        let ruled = self.arbiter == Some(*arbiter)
+            || self
+                .votes
+                .iter()
+                .any(|vote| vote.arbiter == *arbiter && vote.ruling.buyer_bps == 10_000);
+
+        self.verified_merchant
+            && !self.force_settled
+            && self.merchant_slashed == 0
+            && ruled
+            && resolved_at >= bonded_since
+    }
+
+    // a ruling waiting for the appeal window, or a frozen escrow, is not overdue
+    pub fn is_overdue(&self, now: i64) -> bool {
+        !self.is_frozen() && !self.is_awaiting_appeal() && now > self.resolution_deadline
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_at > 0
+    }
+
+    // the deadlines set before the freeze are pushed back by the time frozen
+    pub fn unfreeze(&mut self, frozen_secs: i64) {
+        for deadline in [
+            &mut self.voting_deadline,
+            &mut self.resolution_deadline,
+            &mut self.appeal_deadline,
+        ] {
+            if *deadline > 0 {
+                *deadline = deadline.checked_add(frozen_secs).unwrap();
+            }
+        }
+
+        self.frozen_at = 0;
+    }
+
+    pub fn is_awaiting_appeal(&self) -> bool {
+        self.pending_ruling.is_some() && self.appellant.is_none()
+    }
+
+    // only the party that did not get everything can appeal, within the appeal window
+    pub fn can_appeal(&self, party: &Pubkey, now: i64) -> bool {
+        match self.pending_ruling {
+            Some(ruling) if self.appellant.is_none() && now <= self.appeal_deadline => {
+                (*party == self.buyer && ruling.buyer_bps < 10_000)
+                    || (*party == self.seller && ruling.buyer_bps > 0)
+            }
+            _ => false,
+        }
+    }
+
+    // the final ruling is more favorable to the appellant than the appealed one
+    pub fn is_overturned(&self, buyer_bps: u16) -> bool {
+        match (self.appellant, self.pending_ruling) {
+            (Some(appellant), Some(pending)) if appellant == self.buyer => {
+                buyer_bps > pending.buyer_bps
+            }
+            (Some(_), Some(pending)) => buyer_bps < pending.buyer_bps,
+            _ => false,
+        }
+    }
+
+    // the arbiter's ruling was overturned on appeal, or it was drawn and did not rule
+    // before its deadline (the voting deadline for the panel members), never while frozen
+    // (a force settlement is decided when the escrow was frozen)
+    pub fn is_arbiter_slashable(&self, arbiter: &Pubkey, now: i64) -> bool {
+        if self.slashed_arbiters.contains(arbiter) {
+            return false;
+        }
+
+        let decided_at = match self.outcome {
+            DisputeOutcome::Resolved {
+                buyer_bps,
+                resolved_at,
+                ..
+            } => {
+                if self.appealed_arbiter == Some(*arbiter) && self.is_overturned(buyer_bps) {
+                    return true;
+                }
+
+                if self.force_settled {
+                    self.frozen_at
+                } else {
+                    resolved_at
+                }
+            }
+            DisputeOutcome::Pending if self.is_frozen() => return false,
+            DisputeOutcome::Pending => now,
+        };
+
+        let (drawn, ruled, deadline) = if self.panel {
+            (
+                self.is_panel_member(arbiter),
+                self.has_voted(arbiter),
+                self.voting_deadline,
+            )
+        } else {
+            (
+                self.assigned_arbiter == Some(*arbiter),
+                self.arbiter == Some(*arbiter),
+                self.resolution_deadline,
+            )
+        };
+
+        drawn && !ruled && decided_at > deadline
+    }
+
+    pub fn is_panel_member(&self, arbiter: &Pubkey) -> bool {
+        self.panel_members.contains(arbiter)
+    }
+
+    pub fn has_voted(&self, arbiter: &Pubkey) -> bool {
+        self.votes.iter().any(|vote| vote.arbiter == *arbiter)
+    }
+
+    pub fn count_votes(&self, ruling: &Ruling) -> u8 {
+        self.votes
+            .iter()
+            .filter(|vote| vote.ruling == *ruling)
+            .count() as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Ruling {
+    pub buyer_bps: u16, // share of the escrow for the buyer (10000 = everything)
+    pub refund_buyer_deposit: bool,
+    pub refund_seller_deposit: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PanelVote {
+    pub arbiter: Pubkey,
+    pub ruling: Ruling,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum DisputeOutcome {
+    Pending,
+    Resolved {
+        buyer_bps: u16,
+        buyer_deposit_refunded: u64, // lamports, or tokens of the deposit mint
+        seller_deposit_refunded: u64, // lamports, or tokens of the deposit mint
+        resolved_at: i64,
+    },
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{DISCRIMINATOR_SIZE, MAX_EVIDENCE_URI_LEN};
+
+// append only, grows with every submission
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeEvidence {
+    pub escrow_id: u64,
+    pub bump: u8,
+    #[max_len(0)]
+    pub entries: Vec<EvidenceEntry>,
+}
+
+impl DisputeEvidence {
+    // size without entries
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + DisputeEvidence::INIT_SPACE;
+
+    pub fn entry_space(uri: &str) -> usize {
+        EvidenceEntry::INIT_SPACE - MAX_EVIDENCE_URI_LEN as usize + uri.len()
+    }
+
+    pub fn entries_by(&self, submitter: &Pubkey) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.submitted_by == *submitter)
+            .count()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EvidenceEntry {
+    pub submitted_by: Pubkey,
+    pub content_hash: [u8; 32],
+    #[max_len(MAX_EVIDENCE_URI_LEN)]
+    pub uri: String,
+    pub submitted_at: i64,
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher as sha256;
 
-use crate::{constants::DISCRIMINATOR_SIZE, errors::P2pError};
+use crate::{
+    constants::DISCRIMINATOR_SIZE,
+    errors::P2pError,
+    states::{DisputePolicy, DisputeWindow, SignerSetSnapshot},
+};
 
 #[account]
 #[derive(InitSpace)]
@@ -10,34 +15,200 @@ pub struct Escrow {
     pub buyer: Pubkey,
     pub mint: Pubkey,
     pub amount: u64,
+    pub fee_bps: u16,            // fixed at creation (lower for verified merchants)
+    pub verified_merchant: bool, // created by a verified merchant, its bond backs the disputes
     pub state: EscrowState,
+    pub fiat_deadline: i64, // buyer payment deadline (before the grace period), extendable by the seller
+    pub payment_proof: Option<PaymentProof>, // buyer's claimed proof, set when marked as paid
+    pub buyer_acted: bool, // the buyer signed for the escrow (or marked it as paid), its stats are updated
+    pub buyer_bond: Option<BuyerBond>, // posted when marked as paid
     pub disputed_by: EscrowDisputedBy,
+    pub release_approver: ReleaseApprover,
+    pub signer_set: Option<SignerSetSnapshot>, // seller's signer set at creation (SignerSet approver)
+    pub freeze: Option<EscrowFreeze>,          // set while frozen
     pub bump: u8,
 }
 
 impl Escrow {
     pub const SIZE: usize = DISCRIMINATOR_SIZE + Escrow::INIT_SPACE;
 
-    pub fn can_cancel(&self, fiat_deadline_secs: i64) -> bool {
-        if let EscrowState::Open(timestamp) = self.state {
-            Clock::get().unwrap().unix_timestamp > timestamp + fiat_deadline_secs
+    // deposited by the seller on top of the amount
+    pub fn calculate_fee(&self) -> u64 {
+        self.fee_of(self.amount)
+    }
+
+    // the buyer share pays its pro-rated fee, the rest of the deposited fee goes back to the seller
+    // returns (buyer amount, seller amount, fee)
+    pub fn calculate_split(&self, buyer_bps: u16) -> (u64, u64, u64) {
+        let buyer_share = self
+            .amount
+            .checked_mul(buyer_bps as u64)
+            .unwrap()
+            .checked_div(10_000)
+            .unwrap();
+        let buyer_fee = self.fee_of(buyer_share);
+        let seller_fee = self.calculate_fee().checked_sub(buyer_fee).unwrap();
+
+        (
+            buyer_share.checked_sub(buyer_fee).unwrap(),
+            self.amount
+                .checked_sub(buyer_share)
+                .unwrap()
+                .checked_add(seller_fee)
+                .unwrap(),
+            buyer_fee,
+        )
+    }
+
+    fn fee_of(&self, amount: u64) -> u64 {
+        amount
+            .checked_mul(self.fee_bps as u64)
+            .unwrap()
+            .checked_div(10_000)
+            .unwrap()
+    }
+
+    pub fn can_cancel(&self, fiat_grace_secs: i64) -> bool {
+        if let EscrowState::Open(_) = self.state {
+            let now = Clock::get().unwrap().unix_timestamp;
+
+            // hash locked escrows are refundable only after the lock expires
+            match self.release_approver {
+                ReleaseApprover::HashLock { expires_at, .. } => now > expires_at,
+                _ => now > self.fiat_deadline + fiat_grace_secs,
+            }
+        } else {
+            false
+        }
+    }
+
+    // the buyer can mark as paid until the grace period is over, then the seller can cancel
+    pub fn can_mark_as_paid(&self, fiat_grace_secs: i64) -> bool {
+        Clock::get().unwrap().unix_timestamp <= self.fiat_deadline + fiat_grace_secs
+    }
+
+    pub fn is_in_dispute(&self) -> bool {
+        matches!(
+            self.state,
+            EscrowState::Dispute(_) | EscrowState::ReDispute(_)
+        )
+    }
+
+    // the counterparty did not re-dispute within the response window
+    // (which starts once the re-dispute is allowed, and ends with the re-dispute window)
+    pub fn can_claim_default_judgment(
+        &self,
+        re_dispute_window: &DisputeWindow,
+        dispute_response_secs: i64,
+    ) -> bool {
+        if let EscrowState::Dispute(timestamp) = self.state {
+            let mut deadline = timestamp + re_dispute_window.earliest_secs + dispute_response_secs;
+
+            if let Some(closes_at) = re_dispute_window.closes_at(timestamp) {
+                deadline = deadline.min(closes_at);
+            }
+
+            Clock::get().unwrap().unix_timestamp > deadline
         } else {
             false
         }
     }
 
+    // hash locked escrows are released only with the preimage
+    pub fn can_attested_release(&self) -> bool {
+        matches!(self.state, EscrowState::Open(_) | EscrowState::FiatPaid(_))
+            && !self.is_hash_locked()
+    }
+
+    pub fn is_hash_locked(&self) -> bool {
+        matches!(self.release_approver, ReleaseApprover::HashLock { .. })
+    }
+
+    pub fn can_claim_with_preimage(&self, preimage: &[u8; 32]) -> bool {
+        match self.release_approver {
+            ReleaseApprover::HashLock { hash, expires_at } => {
+                matches!(self.state, EscrowState::Open(_))
+                    && Clock::get().unwrap().unix_timestamp <= expires_at
+                    && sha256::hash(preimage).to_bytes() == hash
+            }
+            _ => false,
+        }
+    }
+
     pub fn can_release(&self) -> bool {
         matches!(self.state, EscrowState::FiatPaid(_))
     }
 
-    pub fn dispute(&mut self, dispute_deadline_secs: i64, disputant: Pubkey) -> Result<()> {
+    pub fn is_party(&self, key: &Pubkey) -> bool {
+        *key == self.buyer || *key == self.seller
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        matches!(self.state, EscrowState::Frozen(_))
+    }
+
+    // restores the state it was frozen in, the time frozen does not count for the deadlines
+    // (returns the time frozen)
+    pub fn unfreeze(&mut self, now: i64) -> Result<i64> {
+        let (EscrowState::Frozen(frozen_at), Some(freeze)) = (&self.state, self.freeze.take())
+        else {
+            return Err(P2pError::EscrowNotFrozen.into());
+        };
+
+        let frozen_secs = now.checked_sub(*frozen_at).unwrap();
+
+        self.state = freeze.prior_state.shifted(frozen_secs);
+        self.fiat_deadline = self.fiat_deadline.checked_add(frozen_secs).unwrap();
+
+        if let ReleaseApprover::HashLock { expires_at, .. } = &mut self.release_approver {
+            *expires_at = expires_at.checked_add(frozen_secs).unwrap();
+        }
+
+        Ok(frozen_secs)
+    }
+
+    // a frozen escrow is force settled by another arbiter than the one that froze it
+    pub fn can_force_settle(&self, arbiter: &Pubkey) -> bool {
+        self.is_frozen()
+            && self
+                .freeze
+                .as_ref()
+                .is_some_and(|freeze| freeze.arbiter != *arbiter)
+    }
+
+    pub fn release_message(escrow_key: &Pubkey) -> String {
+        format!("approve_release:{}", escrow_key) // less than 3000 CU (tested manually)
+    }
+
+    // before any dispute, deposits are not involved
+    pub fn can_mutual_cancel(&self) -> bool {
+        matches!(self.state, EscrowState::Open(_) | EscrowState::FiatPaid(_))
+    }
+
+    pub fn cancel_message(escrow_key: &Pubkey) -> String {
+        format!("approve_cancel:{}", escrow_key)
+    }
+
+    pub fn dispute(&mut self, dispute_policy: &DisputePolicy, disputant: Pubkey) -> Result<()> {
         let current_timestamp = Clock::get()?.unix_timestamp;
 
         match self.state {
+            // the buyer paid fiat but did not mark the escrow as paid
+            // (the buyer bond is posted with the dispute)
+            EscrowState::Open(timestamp) => {
+                require!(dispute_policy.open.enabled, P2pError::EscrowIsNotTaken);
+                require!(disputant == self.buyer, P2pError::UnauthorizedDispute);
+
+                dispute_policy.open.check(timestamp, current_timestamp)?;
+
+                self.disputed_by = EscrowDisputedBy::Buyer;
+                self.state = EscrowState::Dispute(current_timestamp);
+                Ok(())
+            }
             EscrowState::FiatPaid(timestamp) => {
-                if current_timestamp < timestamp + dispute_deadline_secs {
-                    return Err(P2pError::CannotDisputeEscrow.into());
-                }
+                dispute_policy
+                    .fiat_paid
+                    .check(timestamp, current_timestamp)?;
 
                 if disputant == self.seller {
                     self.disputed_by = EscrowDisputedBy::Seller;
@@ -51,9 +222,9 @@ impl Escrow {
                 Ok(())
             }
             EscrowState::Dispute(timestamp) => {
-                if current_timestamp < timestamp + dispute_deadline_secs {
-                    return Err(P2pError::CannotDisputeEscrow.into());
-                }
+                dispute_policy
+                    .re_dispute
+                    .check(timestamp, current_timestamp)?;
 
                 // Only the counterpart can redispute
                 if disputant == self.seller && self.disputed_by == EscrowDisputedBy::Buyer {
@@ -69,11 +240,52 @@ impl Escrow {
                 }
             }
             EscrowState::ReDispute(_) => Err(P2pError::EscrowAlreadyInDispute.into()),
-            EscrowState::Open(_) => Err(P2pError::EscrowIsNotTaken.into()),
+            EscrowState::Frozen(_) => Err(P2pError::EscrowFrozen.into()),
+        }
+    }
+}
+
+// hashes only, the documents stay off-chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct PaymentProof {
+    pub reference: [u8; 32],    // e.g. hash of the bank transaction id
+    pub receipt_hash: [u8; 32], // hash of the receipt content
+}
+
+// held in the dispute vault (lamports) or in the mint vault ata (escrow mint)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BuyerBond {
+    pub amount: u64,
+    pub in_lamports: bool,
+    pub cut_bps: u16, // fixed when posted
+}
+
+impl BuyerBond {
+    // returned to the buyer, or forfeited to the seller minus the protocol cut
+    // returns (buyer amount, seller amount, protocol cut)
+    pub fn split(&self, forfeited: bool) -> (u64, u64, u64) {
+        if !forfeited {
+            return (self.amount, 0, 0);
         }
+
+        let cut = self
+            .amount
+            .checked_mul(self.cut_bps as u64)
+            .unwrap()
+            .checked_div(10_000)
+            .unwrap();
+
+        (0, self.amount.checked_sub(cut).unwrap(), cut)
     }
 }
 
+// restored if the escrow is unfrozen
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct EscrowFreeze {
+    pub arbiter: Pubkey, // who froze it
+    pub prior_state: EscrowState,
+}
+
 // data = timestamp
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum EscrowState {
@@ -81,6 +293,28 @@ pub enum EscrowState {
     FiatPaid(i64),
     Dispute(i64),
     ReDispute(i64),
+    Frozen(i64), // fraud case, only unfreeze, force cancel or force release
+}
+
+impl EscrowState {
+    // same state, changed `secs` later
+    pub fn shifted(&self, secs: i64) -> Self {
+        match *self {
+            EscrowState::Open(timestamp) => EscrowState::Open(timestamp.checked_add(secs).unwrap()),
+            EscrowState::FiatPaid(timestamp) => {
+                EscrowState::FiatPaid(timestamp.checked_add(secs).unwrap())
+            }
+            EscrowState::Dispute(timestamp) => {
+                EscrowState::Dispute(timestamp.checked_add(secs).unwrap())
+            }
+            EscrowState::ReDispute(timestamp) => {
+                EscrowState::ReDispute(timestamp.checked_add(secs).unwrap())
+            }
+            EscrowState::Frozen(timestamp) => {
+                EscrowState::Frozen(timestamp.checked_add(secs).unwrap())
+            }
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -89,3 +323,23 @@ pub enum EscrowDisputedBy {
     Seller,
     Buyer,
 }
+
+// who has to approve the release of the escrow tokens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ReleaseApprover {
+    Seller,
+    SignerSet, // seller's signer set at creation (M of N)
+    Ethereum {
+        address: [u8; 20],
+    }, // EIP-191 signature from an Ethereum address
+    // WebAuthn assertion from a P-256 (compressed) key, for this relying party and origin
+    Passkey {
+        pubkey: [u8; 33],
+        rp_id_hash: [u8; 32],  // sha256(rp id)
+        origin_hash: [u8; 32], // sha256(origin)
+    },
+    HashLock {
+        hash: [u8; 32],
+        expires_at: i64,
+    }, // sha256 preimage, refundable after expires_at
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::DISCRIMINATOR_SIZE;
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_PANEL_SIZE},
+    errors::P2pError,
+    states::Ruling,
+};
 
 #[account]
 #[derive(InitSpace)]
@@ -9,18 +13,55 @@ pub struct GlobalConfig {
     pub escrow_count: u64,
     pub fee_bps: u16,
     pub fiat_deadline_secs: i64,
-    pub dispute_deadline_secs: i64,
+    pub fiat_grace_secs: i64, // extra time to mark as paid after the fiat deadline
+    pub dispute_policy: DisputePolicy, // when each escrow state can be disputed
     pub dispute_fee_escrow: u64, // lamports
+    pub dispute_response_secs: i64, // time the counterparty has to re-dispute
     pub available_lamports: u64, // lamports available for withdrawal
+    pub panel_size: u8,       // arbiters seats on a dispute panel
+    pub panel_quorum: u8,     // matching votes needed to settle a panel dispute
+    pub panel_voting_secs: i64, // time the panel has to reach the quorum
+    pub min_arbiter_stake: u64, // lamports an arbiter must stake to be eligible
+    pub arbiter_reward_bps: u16, // share of the forfeited deposits for the arbiters
+    pub resolution_sla_secs: i64, // time the arbiters have to resolve a re-dispute
+    pub fallback_arbiter: Pubkey, // can resolve once the resolution deadline has passed
+    pub default_ruling: Ruling, // executable by anyone once the resolution deadline has passed
+    pub appeal_window_secs: i64, // 0 = appeals disabled, rulings settle immediately
+    pub appeal_bond_bps: u16, // % of the appellant's dispute deposit, paid in the same mint
+    pub merchant_min_bond: u64, // lamports to be a verified merchant (0 = disabled)
+    pub merchant_fee_bps: u16, // fee of the verified merchants escrows
     pub bump: u8,
 }
 
 impl GlobalConfig {
     pub const SIZE: usize = DISCRIMINATOR_SIZE + GlobalConfig::INIT_SPACE;
 
-    pub fn calculate_fee(&self, amount: u64) -> u64 {
-        amount
-            .checked_mul(self.fee_bps as u64)
+    // verified merchants never pay more than the standard fee
+    pub fn fee_bps_for(&self, verified_merchant: bool) -> u16 {
+        if verified_merchant {
+            self.merchant_fee_bps.min(self.fee_bps)
+        } else {
+            self.fee_bps
+        }
+    }
+
+    // the quorum must be a strict majority of the panel
+    pub fn validate_panel(&self) -> Result<()> {
+        require!(
+            self.panel_size <= MAX_PANEL_SIZE
+                && self.panel_quorum <= self.panel_size
+                && self.panel_quorum as u16 * 2 > self.panel_size as u16
+                && self.panel_voting_secs > 0,
+            P2pError::InvalidPanelConfig
+        );
+
+        Ok(())
+    }
+
+    // lamports, or tokens of the forfeited token deposits
+    pub fn calculate_arbiter_reward(&self, forfeited: u64) -> u64 {
+        forfeited
+            .checked_mul(self.arbiter_reward_bps as u64)
             .unwrap()
             .checked_div(10_000)
             .unwrap()
@@ -30,10 +71,59 @@ impl GlobalConfig {
         self.escrow_count = self.escrow_count.checked_add(1).unwrap();
     }
 
-    pub fn add_available_lamports(&mut self) {
-        self.available_lamports = self
-            .available_lamports
-            .checked_add(self.dispute_fee_escrow)
-            .unwrap();
+    pub fn add_available_lamports(&mut self, lamports: u64) {
+        self.available_lamports = self.available_lamports.checked_add(lamports).unwrap();
+    }
+}
+
+// dispute windows per escrow state, counted from the last state change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct DisputePolicy {
+    pub open: DisputeWindow, // buyer only, fiat paid off-chain but not marked as paid
+    pub fiat_paid: DisputeWindow,
+    pub re_dispute: DisputeWindow,
+}
+
+impl DisputePolicy {
+    // disputes must always be answerable by the counterparty
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.open.is_valid() && self.fiat_paid.is_valid() && self.re_dispute.is_valid(),
+            P2pError::InvalidDisputePolicy
+        );
+        require!(self.re_dispute.enabled, P2pError::InvalidDisputePolicy);
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct DisputeWindow {
+    pub enabled: bool,
+    pub earliest_secs: i64,
+    pub latest_secs: i64, // 0 = no limit
+}
+
+impl DisputeWindow {
+    pub fn is_valid(&self) -> bool {
+        self.earliest_secs >= 0 && (self.latest_secs == 0 || self.latest_secs >= self.earliest_secs)
+    }
+
+    pub fn check(&self, since: i64, now: i64) -> Result<()> {
+        require!(
+            self.enabled && now >= since + self.earliest_secs,
+            P2pError::CannotDisputeEscrow
+        );
+        require!(
+            self.latest_secs == 0 || now <= since + self.latest_secs,
+            P2pError::DisputeWindowClosed
+        );
+
+        Ok(())
+    }
+
+    // last moment to dispute, none when there is no limit
+    pub fn closes_at(&self, since: i64) -> Option<i64> {
+        (self.latest_secs > 0).then(|| since + self.latest_secs)
     }
 }
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::DISCRIMINATOR_SIZE;
+
+// seller bond (lamports held in the dispute vault), slashable in the disputes the merchant loses
+#[account]
+#[derive(InitSpace)]
+pub struct MerchantProfile {
+    pub merchant: Pubkey,
+    pub bond: u64,
+    pub unbonding_until: i64,  // 0 = not unbonding
+    pub slashed: u64,          // total paid to the buyers
+    pub bonded_since: i64,     // first stake of the current bond, older disputes are not slashable
+    pub pending_disputes: u32, // disputes of the verified escrows not settled yet, block the unbonding
+    pub bump: u8,
+}
+
+impl MerchantProfile {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + MerchantProfile::INIT_SPACE;
+
+    // the verified tier is disabled while the minimum bond is 0
+    pub fn is_verified(&self, merchant_min_bond: u64) -> bool {
+        merchant_min_bond > 0 && self.bond >= merchant_min_bond && self.unbonding_until == 0
+    }
+
+    // counts the disputes of the escrows created as a verified merchant (opened or settled),
+    // the profile is read and written manually, the account may not exist
+    pub fn record_pending_dispute(profile: &AccountInfo, opened: bool) -> Result<()> {
+        if profile.data_is_empty() {
+            return Ok(());
+        }
+
+        require_keys_eq!(
+            *profile.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+
+        let mut merchant_profile =
+            MerchantProfile::try_deserialize(&mut &profile.try_borrow_data()?[..])?;
+
+        merchant_profile.pending_disputes = if opened {
+            merchant_profile.pending_disputes.checked_add(1).unwrap()
+        } else {
+            merchant_profile.pending_disputes.saturating_sub(1)
+        };
+
+        merchant_profile.try_serialize(&mut &mut profile.try_borrow_mut_data()?[..])
+    }
+}
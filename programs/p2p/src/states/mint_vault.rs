@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::DISCRIMINATOR_SIZE;
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_ARBITERS},
+    states::{ArbiterRegistry, BuyerBond},
+};
 
 #[account]
 #[derive(InitSpace)]
 pub struct MintVault {
     pub mint: Pubkey,
     pub available_amount: u64, // available amount to withdraw
+    pub panel_threshold: u64,  // disputes above this amount go to a panel (0 = disabled)
+    pub dispute_bond_bps: u16, // dispute bond in this mint, % of the escrow amount
+    pub dispute_bond_floor: u64,
+    pub dispute_bond_cap: u64,        // 0 = no cap
+    pub buyer_bond: u64,              // posted when marking as paid (0 = disabled)
+    pub buyer_bond_in_lamports: bool, // in lamports instead of this mint
+    pub buyer_bond_cut_bps: u16,      // protocol cut when the bond is forfeited to the seller
+    pub escrow_limit: u64,            // max escrow amount (0 = no limit)
+    pub merchant_escrow_limit: u64,   // max escrow amount of the verified merchants (0 = no limit)
+    #[max_len(MAX_ARBITERS)]
+    pub arbiter_rewards: Vec<ArbiterTokenReward>, // claimable shares of the forfeited tokens
     pub is_initialized: bool,
     pub bump: u8,
 }
@@ -14,7 +28,123 @@ pub struct MintVault {
 impl MintVault {
     pub const SIZE: usize = DISCRIMINATOR_SIZE + MintVault::INIT_SPACE;
 
+    // created with `init_if_needed` by the first escrow or config of the mint, no limits,
+    // bonds or panel until the authority sets them
+    pub fn init_if_needed(&mut self, mint: Pubkey, bump: u8) {
+        if self.is_initialized {
+            return;
+        }
+
+        *self = MintVault {
+            is_initialized: true,
+            mint,
+            available_amount: 0,
+            panel_threshold: 0,
+            dispute_bond_bps: 0,
+            dispute_bond_floor: 0,
+            dispute_bond_cap: 0,
+            buyer_bond: 0,
+            buyer_bond_in_lamports: false,
+            buyer_bond_cut_bps: 0,
+            escrow_limit: 0,
+            merchant_escrow_limit: 0,
+            arbiter_rewards: Vec::new(),
+            bump,
+        };
+    }
+
     pub fn add_available_amount(&mut self, amount: u64) {
         self.available_amount = self.available_amount.checked_add(amount).unwrap();
     }
+
+    // splits the reward between the registered arbiters (the resolution is recorded in the registry)
+    // returns the amount actually credited, kept in the mint vault ata until claimed
+    pub fn reward_arbiters(
+        &mut self,
+        keys: &[Pubkey],
+        amount: u64,
+        arbiter_registry: &ArbiterRegistry,
+    ) -> u64 {
+        if keys.is_empty() {
+            return 0;
+        }
+
+        let share = amount / keys.len() as u64;
+        let mut credited = 0;
+
+        for key in keys {
+            if arbiter_registry.get(key).is_none() {
+                continue;
+            }
+
+            match self
+                .arbiter_rewards
+                .iter_mut()
+                .find(|reward| reward.arbiter == *key)
+            {
+                Some(reward) => reward.amount = reward.amount.checked_add(share).unwrap(),
+                // at most one entry per registered arbiter
+                None => self.arbiter_rewards.push(ArbiterTokenReward {
+                    arbiter: *key,
+                    amount: share,
+                }),
+            }
+
+            credited += share;
+        }
+
+        credited
+    }
+
+    // resets the arbiter's reward, returns the claimed amount
+    pub fn take_arbiter_reward(&mut self, arbiter: &Pubkey) -> u64 {
+        self.arbiter_rewards
+            .iter_mut()
+            .find(|reward| reward.arbiter == *arbiter)
+            .map_or(0, |reward| std::mem::take(&mut reward.amount))
+    }
+
+    pub fn requires_panel(&self, amount: u64) -> bool {
+        self.panel_threshold > 0 && amount > self.panel_threshold
+    }
+
+    pub fn is_within_limit(&self, amount: u64, verified_merchant: bool) -> bool {
+        let limit = if verified_merchant {
+            self.merchant_escrow_limit
+        } else {
+            self.escrow_limit
+        };
+
+        limit == 0 || amount <= limit
+    }
+
+    pub fn buyer_bond(&self) -> Option<BuyerBond> {
+        (self.buyer_bond > 0).then_some(BuyerBond {
+            amount: self.buyer_bond,
+            in_lamports: self.buyer_bond_in_lamports,
+            cut_bps: self.buyer_bond_cut_bps,
+        })
+    }
+
+    // none when the dispute bond is paid in lamports (no token bond configured)
+    pub fn calculate_dispute_bond(&self, amount: u64) -> Option<u64> {
+        if self.dispute_bond_bps == 0 && self.dispute_bond_floor == 0 {
+            return None;
+        }
+
+        let bond = (amount as u128 * self.dispute_bond_bps as u128 / 10_000) as u64;
+        let bond = bond.max(self.dispute_bond_floor);
+
+        if self.dispute_bond_cap > 0 {
+            Some(bond.min(self.dispute_bond_cap))
+        } else {
+            Some(bond)
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ArbiterTokenReward {
+    pub arbiter: Pubkey,
+    pub amount: u64,
 }
@@ -1,7 +1,21 @@
+pub mod arbiter_registry;
+pub mod dispute;
+pub mod dispute_evidence;
 pub mod escrow;
 pub mod global_config;
+pub mod merchant_profile;
 pub mod mint_vault;
+pub mod payment_attester;
+pub mod signer_set;
+pub mod user_stats;
 
+pub use arbiter_registry::*;
+pub use dispute::*;
+pub use dispute_evidence::*;
 pub use escrow::*;
 pub use global_config::*;
+pub use merchant_profile::*;
 pub use mint_vault::*;
+pub use payment_attester::*;
+pub use signer_set::*;
+pub use user_stats::*;
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::DISCRIMINATOR_SIZE;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentAttester {
+    pub attester: Pubkey,
+    pub active: bool,
+    pub bump: u8,
+}
+
+impl PaymentAttester {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + PaymentAttester::INIT_SPACE;
+
+    pub fn attestation_message(
+        escrow_key: &Pubkey,
+        fiat_amount: u64,
+        reference: &[u8; 32],
+    ) -> String {
+        let reference: String = reference
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        format!(
+            "attest_payment:{}:{}:{}",
+            escrow_key, fiat_amount, reference
+        )
+    }
+}
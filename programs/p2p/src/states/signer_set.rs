@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_SIGNER_SET_SIGNERS},
+    errors::P2pError,
+    utils::verify_ed25519_signature,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct SignerSet {
+    pub owner: Pubkey,
+    pub threshold: u8,
+    #[max_len(MAX_SIGNER_SET_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl SignerSet {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + SignerSet::INIT_SPACE;
+
+    pub fn validate(threshold: u8, signers: &[Pubkey]) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_SIGNER_SET_SIGNERS as usize,
+            P2pError::InvalidSignerSet
+        );
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            P2pError::InvalidSignerSet
+        );
+
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), P2pError::InvalidSignerSet);
+        }
+
+        Ok(())
+    }
+
+    // escrows keep the set they were created with, later changes only apply to new escrows
+    pub fn snapshot(&self) -> SignerSetSnapshot {
+        SignerSetSnapshot {
+            threshold: self.threshold,
+            signers: self.signers.clone(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct SignerSetSnapshot {
+    pub threshold: u8,
+    #[max_len(MAX_SIGNER_SET_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+}
+
+impl SignerSetSnapshot {
+    // each approval must come from a distinct signer of the set
+    pub fn verify_approvals(&self, message: &[u8], approvals: &[SignerApproval]) -> Result<()> {
+        let mut approved: u16 = 0; // bitmask of signer indexes
+
+        for approval in approvals {
+            let signer = self
+                .signers
+                .get(approval.signer_index as usize)
+                .ok_or(P2pError::InvalidSignerIndex)?;
+
+            let bit = 1u16 << approval.signer_index;
+            require!(approved & bit == 0, P2pError::DuplicateApproval);
+
+            verify_ed25519_signature(signer, &approval.signature, message)?;
+
+            approved |= bit;
+        }
+
+        require!(
+            approved.count_ones() >= self.threshold as u32,
+            P2pError::NotEnoughApprovals
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignerApproval {
+    pub signer_index: u8,
+    pub signature: [u8; 64],
+}
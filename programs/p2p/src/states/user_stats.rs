@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISCRIMINATOR_SIZE, MAX_USER_STATS_MINTS},
+    states::Escrow,
+};
+
+// per wallet reputation, updated by the program on every settlement (escrows are closed),
+// the buyer's only if it acted on the escrow (a wallet named as the buyer of escrows it never
+// signed for is not affected)
+#[account]
+#[derive(InitSpace)]
+pub struct UserStats {
+    pub user: Pubkey,
+    pub trades_as_buyer: u32,
+    pub trades_as_seller: u32,
+    #[max_len(MAX_USER_STATS_MINTS)]
+    pub volumes: Vec<MintVolume>, // released amount per mint, first mints traded only
+    pub untracked_trades: u32, // trades in the mints beyond the tracked ones
+    pub disputes_opened: u32,
+    pub disputes_won: u32,  // whole escrow awarded to the user
+    pub disputes_lost: u32, // whole escrow awarded to the counterparty
+    pub cancellations: u32, // timeout, mutual or forced cancels, both parties
+    pub abandons: u32,      // buyer withdrew before paying
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct MintVolume {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl UserStats {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE + UserStats::INIT_SPACE;
+
+    // created with `init_if_needed` by the instruction that updates it, which sets the user
+    // and the bump (from `ctx.bumps`) before recording
+    pub fn init_if_needed(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record_trade(
+        buyer_stats: &mut UserStats,
+        seller_stats: &mut UserStats,
+        escrow: &Escrow,
+    ) {
+        if escrow.buyer_acted {
+            buyer_stats.trades_as_buyer = buyer_stats.trades_as_buyer.saturating_add(1);
+            buyer_stats.add_volume(escrow.mint, escrow.amount);
+        }
+
+        seller_stats.trades_as_seller = seller_stats.trades_as_seller.saturating_add(1);
+        seller_stats.add_volume(escrow.mint, escrow.amount);
+    }
+
+    pub fn record_cancellation(
+        buyer_stats: &mut UserStats,
+        seller_stats: &mut UserStats,
+        escrow: &Escrow,
+    ) {
+        if escrow.buyer_acted {
+            buyer_stats.cancellations = buyer_stats.cancellations.saturating_add(1);
+        }
+
+        seller_stats.cancellations = seller_stats.cancellations.saturating_add(1);
+    }
+
+    // split rulings are neither won nor lost
+    pub fn record_dispute_outcome(
+        buyer_stats: &mut UserStats,
+        seller_stats: &mut UserStats,
+        escrow: &Escrow,
+        buyer_bps: u16,
+    ) {
+        let (buyer_won, buyer_lost) = match buyer_bps {
+            10_000 => (1, 0),
+            0 => (0, 1),
+            _ => return,
+        };
+
+        if escrow.buyer_acted {
+            buyer_stats.disputes_won = buyer_stats.disputes_won.saturating_add(buyer_won);
+            buyer_stats.disputes_lost = buyer_stats.disputes_lost.saturating_add(buyer_lost);
+        }
+
+        seller_stats.disputes_won = seller_stats.disputes_won.saturating_add(buyer_lost);
+        seller_stats.disputes_lost = seller_stats.disputes_lost.saturating_add(buyer_won);
+    }
+
+    pub fn record_dispute_opened(&mut self) {
+        self.disputes_opened = self.disputes_opened.saturating_add(1);
+    }
+
+    pub fn record_abandon(&mut self) {
+        self.abandons = self.abandons.saturating_add(1);
+    }
+
+    // the volume of the mints beyond the first ones is not tracked, the trades are counted
+    fn add_volume(&mut self, mint: Pubkey, amount: u64) {
+        if let Some(volume) = self.volumes.iter_mut().find(|volume| volume.mint == mint) {
+            volume.amount = volume.amount.saturating_add(amount);
+        } else if self.volumes.len() < MAX_USER_STATS_MINTS as usize {
+            self.volumes.push(MintVolume { mint, amount });
+        } else {
+            self.untracked_trades = self.untracked_trades.saturating_add(1);
+        }
+    }
+}
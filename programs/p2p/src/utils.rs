@@ -0,0 +1,258 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::TokenAccount;
+use solana_sha256_hasher as sha256;
+
+use crate::{
+    constants::{DISPUTE_VAULT_SEED, MINT_VAULT_SEED},
+    errors::P2pError,
+    events,
+    states::{BuyerBond, Escrow, GlobalConfig, MintVault},
+};
+
+pub fn verify_ed25519_signature(
+    pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<()> {
+    brine_ed25519::sig_verify(&pubkey.to_bytes(), signature, message).map_err(|err| {
+        msg!("Signature verification failed {:?}", err);
+        P2pError::SignatureVerificationFailed
+    })?;
+
+    Ok(())
+}
+
+// pseudo random seed from the hash of the first slot at or after `slot` and the escrow id,
+// none if that hash is not in the SlotHashes sysvar (not produced yet, or older than 512 slots)
+// layout: u64 entries count, then (u64 slot, [u8; 32] hash) entries, most recent first
+pub fn slot_hash_seed(slot_hashes: &AccountInfo, slot: u64, escrow_id: u64) -> Result<Option<u64>> {
+    const ENTRY_SIZE: usize = 40;
+
+    let data = slot_hashes.try_borrow_data()?;
+    let read_u64 = |at: usize| {
+        data.get(at..at + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidAccountData)
+    };
+
+    let count = read_u64(0)? as usize;
+    let slot_at = |index: usize| read_u64(8 + index * ENTRY_SIZE);
+
+    // number of entries at or after `slot` (binary search, slots are descending)
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = (low + high) / 2;
+        if slot_at(mid)? >= slot {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    // the first slot after `slot` may have been evicted if every entry is more recent
+    if low == 0 || (low == count && slot_at(count - 1)? != slot) {
+        return Ok(None);
+    }
+
+    let hash_at = 8 + (low - 1) * ENTRY_SIZE + 8;
+    let hash = data
+        .get(hash_at..hash_at + 32)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let seed = sha256::hashv(&[hash, &escrow_id.to_le_bytes()]).to_bytes();
+
+    Ok(Some(u64::from_le_bytes(seed[..8].try_into().unwrap())))
+}
+
+// lamports held by the dispute vault (dispute deposits, buyer bonds)
+pub fn transfer_from_dispute_vault<'info>(
+    dispute_vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    lamports: u64,
+    dispute_vault_bump: u8,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[DISPUTE_VAULT_SEED, &[dispute_vault_bump]]];
+
+    let cpi_accounts = system_program::Transfer {
+        from: dispute_vault,
+        to,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+
+    system_program::transfer(cpi_ctx, lamports)
+}
+
+// tokens of the dispute vault ata (token dispute deposits and appeal bonds)
+pub fn transfer_tokens_from_dispute_vault<'info>(
+    dispute_vault: AccountInfo<'info>,
+    dispute_vault_ata: Option<&InterfaceAccount<'info, TokenAccount>>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    dispute_vault_bump: u8,
+) -> Result<()> {
+    let Some(dispute_vault_ata) = dispute_vault_ata else {
+        return Err(P2pError::MissingTokenAccount.into());
+    };
+
+    let signer_seeds: &[&[&[u8]]] = &[&[DISPUTE_VAULT_SEED, &[dispute_vault_bump]]];
+
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: dispute_vault_ata.to_account_info(),
+        to,
+        authority: dispute_vault,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    anchor_spl::token::transfer(cpi_ctx, amount)
+}
+
+// tokens of the mint vault ata (escrow tokens, token buyer bonds)
+pub fn transfer_from_mint_vault<'info>(
+    mint_vault: &Account<'info, MintVault>,
+    mint_vault_ata: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MINT_VAULT_SEED,
+        mint_vault.mint.as_ref(),
+        &[mint_vault.bump],
+    ]];
+
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: mint_vault_ata,
+        to,
+        authority: mint_vault.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    anchor_spl::token::transfer(cpi_ctx, amount)
+}
+
+// the buyer bond is held in the dispute vault (lamports) or in the mint vault ata (escrow mint),
+// the token accounts are required only for a token bond
+pub fn post_buyer_bond<'info>(
+    bond: &BuyerBond,
+    buyer: AccountInfo<'info>,
+    buyer_ata: Option<AccountInfo<'info>>,
+    dispute_vault: AccountInfo<'info>,
+    mint_vault_ata: Option<AccountInfo<'info>>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if bond.in_lamports {
+        let cpi_accounts = system_program::Transfer {
+            from: buyer,
+            to: dispute_vault,
+        };
+
+        let cpi_ctx = CpiContext::new(system_program, cpi_accounts);
+
+        return system_program::transfer(cpi_ctx, bond.amount);
+    }
+
+    let (Some(buyer_ata), Some(mint_vault_ata)) = (buyer_ata, mint_vault_ata) else {
+        return Err(P2pError::MissingTokenAccount.into());
+    };
+
+    let cpi_accounts = anchor_spl::token::Transfer {
+        from: buyer_ata,
+        to: mint_vault_ata,
+        authority: buyer,
+    };
+
+    let cpi_ctx = CpiContext::new(token_program, cpi_accounts);
+
+    anchor_spl::token::transfer(cpi_ctx, bond.amount)
+}
+
+// accounts holding or receiving the buyer bond when an escrow is settled
+pub struct BuyerBondAccounts<'a, 'info> {
+    pub buyer: AccountInfo<'info>,
+    pub buyer_ata: AccountInfo<'info>,
+    pub seller: AccountInfo<'info>,
+    pub seller_ata: Option<AccountInfo<'info>>, // required only if a token bond is forfeited
+    pub global_config: &'a mut Account<'info, GlobalConfig>,
+    pub dispute_vault: AccountInfo<'info>,
+    pub dispute_vault_bump: u8,
+    pub mint_vault: &'a mut Account<'info, MintVault>,
+    pub mint_vault_ata: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// the buyer bond is returned, or forfeited to the seller minus the protocol cut
+pub fn settle_buyer_bond(
+    escrow: &Escrow,
+    accounts: BuyerBondAccounts,
+    forfeited: bool,
+) -> Result<()> {
+    let Some(bond) = &escrow.buyer_bond else {
+        return Ok(());
+    };
+
+    let (returned, seller_amount, protocol_cut) = bond.split(forfeited);
+
+    for (to, to_ata, amount) in [
+        (
+            accounts.buyer.clone(),
+            Some(accounts.buyer_ata.clone()),
+            returned,
+        ),
+        (
+            accounts.seller.clone(),
+            accounts.seller_ata.clone(),
+            seller_amount,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        if bond.in_lamports {
+            transfer_from_dispute_vault(
+                accounts.dispute_vault.clone(),
+                to,
+                accounts.system_program.clone(),
+                amount,
+                accounts.dispute_vault_bump,
+            )?;
+        } else {
+            let Some(to_ata) = to_ata else {
+                return Err(P2pError::MissingTokenAccount.into());
+            };
+
+            transfer_from_mint_vault(
+                accounts.mint_vault,
+                accounts.mint_vault_ata.clone(),
+                to_ata,
+                accounts.token_program.clone(),
+                amount,
+            )?;
+        }
+    }
+
+    // the cut stays in the vault holding the bond
+    if bond.in_lamports {
+        accounts.global_config.add_available_lamports(protocol_cut);
+    } else {
+        accounts.mint_vault.add_available_amount(protocol_cut);
+    }
+
+    emit!(events::BuyerBondSettled {
+        id: escrow.id,
+        buyer: escrow.buyer,
+        returned,
+        forfeited: seller_amount,
+        protocol_cut,
+        in_lamports: bond.in_lamports,
+    });
+
+    Ok(())
+}
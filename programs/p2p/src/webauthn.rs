@@ -0,0 +1,473 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::secp256r1_program;
+use solana_sha256_hasher as sha256;
+
+use crate::errors::P2pError;
+
+// WebAuthn assertion whose challenge is sha256(release message)
+pub fn verify_passkey_assertion(
+    instructions_sysvar: &AccountInfo,
+    pubkey: &[u8; 33],
+    rp_id_hash: &[u8; 32],
+    origin_hash: &[u8; 32],
+    message: &[u8],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+) -> Result<()> {
+    // rp id hash (32) + flags (1) + sign count (4), user present flag must be set
+    require!(
+        authenticator_data.len() >= 37
+            && authenticator_data[..32] == rp_id_hash[..]
+            && authenticator_data[32] & 0x01 != 0,
+        P2pError::InvalidWebAuthnAssertion
+    );
+
+    let client_data =
+        ClientData::parse(client_data_json).ok_or(P2pError::InvalidWebAuthnAssertion)?;
+    let challenge = base64url_encode(&sha256::hash(message).to_bytes());
+
+    require!(
+        client_data.kind == "webauthn.get"
+            && client_data.challenge == challenge
+            && sha256::hash(client_data.origin.as_bytes()).to_bytes() == *origin_hash,
+        P2pError::InvalidWebAuthnAssertion
+    );
+
+    // signed data = authenticatorData || sha256(clientDataJSON)
+    let signed_data = [
+        authenticator_data,
+        &sha256::hash(client_data_json).to_bytes(),
+    ]
+    .concat();
+
+    // look for a secp256r1 precompile instruction (earlier in the tx) over the signed data
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+
+        if instruction.program_id == secp256r1_program::ID
+            && secp256r1_verifies(&instruction.data, pubkey, &signed_data)
+        {
+            return Ok(());
+        }
+    }
+
+    Err(P2pError::MissingSecp256r1Verification.into())
+}
+
+// layout: [num_signatures: u8, padding: u8, offsets: [u16; 7] * num_signatures, ...data]
+fn secp256r1_verifies(data: &[u8], pubkey: &[u8; 33], signed_data: &[u8]) -> bool {
+    const HEADER_SIZE: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+
+    let num_signatures = match data.first() {
+        Some(num_signatures) => *num_signatures as usize,
+        None => return false,
+    };
+
+    (0..num_signatures).any(|i| {
+        let start = HEADER_SIZE + i * OFFSETS_SIZE;
+        let read_u16 = |at: usize| {
+            data.get(start + at..start + at + 2)
+                .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        };
+
+        // signature, pubkey and message must all live in the precompile instruction itself
+        let (
+            Some(u16::MAX),
+            Some(pubkey_offset),
+            Some(u16::MAX),
+            Some(message_offset),
+            Some(message_size),
+            Some(u16::MAX),
+        ) = (
+            read_u16(2),
+            read_u16(4),
+            read_u16(6),
+            read_u16(8),
+            read_u16(10),
+            read_u16(12),
+        )
+        else {
+            return false;
+        };
+
+        let pubkey_offset = pubkey_offset as usize;
+        let message_offset = message_offset as usize;
+
+        data.get(pubkey_offset..pubkey_offset + pubkey.len()) == Some(&pubkey[..])
+            && data.get(message_offset..message_offset + message_size as usize) == Some(signed_data)
+    })
+}
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+
+        // no padding
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+// top level members of clientDataJSON used by the assertion
+struct ClientData {
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+impl ClientData {
+    // strict JSON object: other members (and nested values) are skipped, duplicates rejected
+    fn parse(json: &[u8]) -> Option<Self> {
+        core::str::from_utf8(json).ok()?;
+
+        let mut parser = JsonParser { json, at: 0 };
+        let (mut kind, mut challenge, mut origin) = (None, None, None);
+
+        parser.expect(b'{')?;
+
+        if !parser.eat(b'}') {
+            loop {
+                let key = parser.string()?;
+                parser.expect(b':')?;
+
+                let member = match key.as_str() {
+                    "type" => Some(&mut kind),
+                    "challenge" => Some(&mut challenge),
+                    "origin" => Some(&mut origin),
+                    _ => None,
+                };
+
+                match member {
+                    Some(member) if member.is_none() => *member = Some(parser.string()?),
+                    Some(_) => return None,
+                    None => parser.skip_value(0)?,
+                }
+
+                if parser.eat(b'}') {
+                    break;
+                }
+                parser.expect(b',')?;
+            }
+        }
+
+        parser.skip_whitespace();
+        if parser.at != json.len() {
+            return None;
+        }
+
+        Some(Self {
+            kind: kind?,
+            challenge: challenge?,
+            origin: origin?,
+        })
+    }
+}
+
+struct JsonParser<'a> {
+    json: &'a [u8],
+    at: usize,
+}
+
+impl JsonParser<'_> {
+    const MAX_DEPTH: u8 = 8;
+
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.at).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.at += 1;
+        Some(byte)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.at += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.at += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect(b'"')?;
+
+        let mut value = String::new();
+
+        loop {
+            // the input is valid UTF-8 and the run ends before an ASCII byte
+            let start = self.at;
+            while !matches!(self.peek()?, b'"' | b'\\' | 0..=0x1f) {
+                self.at += 1;
+            }
+            value.push_str(core::str::from_utf8(&self.json[start..self.at]).ok()?);
+
+            match self.next()? {
+                b'"' => return Some(value),
+                b'\\' => value.push(self.escape()?),
+                _ => return None, // control characters must be escaped
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Option<char> {
+        let escaped = match self.next()? {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.hex4()?;
+
+                if (0xd800..0xdc00).contains(&high) {
+                    // surrogate pair
+                    if self.next()? != b'\\' || self.next()? != b'u' {
+                        return None;
+                    }
+                    let low = self.hex4()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return None;
+                    }
+                    char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))?
+                } else {
+                    char::from_u32(high)?
+                }
+            }
+            _ => return None,
+        };
+
+        Some(escaped)
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.json.get(self.at..self.at + 4)?;
+        if !digits.iter().all(u8::is_ascii_hexdigit) {
+            return None;
+        }
+        self.at += 4;
+
+        u32::from_str_radix(core::str::from_utf8(digits).ok()?, 16).ok()
+    }
+
+    fn skip_value(&mut self, depth: u8) -> Option<()> {
+        if depth >= Self::MAX_DEPTH {
+            return None;
+        }
+
+        self.skip_whitespace();
+
+        match self.peek()? {
+            b'"' => {
+                self.string()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.at += 1;
+
+                if !self.eat(close) {
+                    loop {
+                        if open == b'{' {
+                            self.string()?;
+                            self.expect(b':')?;
+                        }
+                        self.skip_value(depth + 1)?;
+
+                        if self.eat(close) {
+                            break;
+                        }
+                        self.expect(b',')?;
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => self.number()?,
+            _ => {
+                // true, false and null, not followed by more letters
+                let literal = [&b"true"[..], b"false", b"null"]
+                    .into_iter()
+                    .find(|literal| self.json[self.at..].starts_with(literal))?;
+                self.at += literal.len();
+
+                if self.peek().is_some_and(|byte| byte.is_ascii_alphanumeric()) {
+                    return None;
+                }
+            }
+        }
+
+        Some(())
+    }
+    // -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+    fn number(&mut self) -> Option<()> {
+        if self.peek() == Some(b'-') {
+            self.at += 1;
+        }
+
+        match self.peek()? {
+            b'0' => self.at += 1,
+            b'1'..=b'9' => {
+                self.digits();
+            }
+            _ => return None,
+        }
+
+        if self.peek() == Some(b'.') {
+            self.at += 1;
+            if self.digits() == 0 {
+                return None;
+            }
+        }
+
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.at += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.at += 1;
+            }
+            if self.digits() == 0 {
+                return None;
+            }
+        }
+
+        // a number runs into the next separator, e.g. `1abc` or `01` are rejected
+        if self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || byte == b'.')
+        {
+            return None;
+        }
+
+        Some(())
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.at;
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.at += 1;
+        }
+        self.at - start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_data(extra: &str) -> Option<ClientData> {
+        let json = format!(
+            r#"{{"type":"webauthn.get","challenge":"abc","origin":"https://p2p.example"{extra}}}"#
+        );
+        ClientData::parse(json.as_bytes())
+    }
+
+    fn skips(value: &str) -> bool {
+        client_data(&format!(r#","extra":{value}"#)).is_some()
+    }
+
+    #[test]
+    fn parses_client_data() {
+        let client_data = client_data("").unwrap();
+
+        assert_eq!(client_data.kind, "webauthn.get");
+        assert_eq!(client_data.challenge, "abc");
+        assert_eq!(client_data.origin, "https://p2p.example");
+    }
+
+    #[test]
+    fn rejects_missing_members_and_trailing_data() {
+        assert!(ClientData::parse(br#"{"type":"webauthn.get","challenge":"abc"}"#).is_none());
+        assert!(ClientData::parse(b"{}").is_none());
+        assert!(client_data("}").is_none());
+        assert!(client_data(",").is_none());
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        let json = br#"{"type":"webauthn.get","challenge":"a\"\\\/\b\f\n\r\t\u0041","origin":"o"}"#;
+
+        assert_eq!(
+            ClientData::parse(json).unwrap().challenge,
+            "a\"\\/\u{8}\u{c}\n\r\tA"
+        );
+        assert!(!skips(r#""\x""#));
+        assert!(!skips(r#""\u00g1""#));
+        assert!(!skips("\"\n\"")); // raw control character
+    }
+
+    #[test]
+    fn decodes_surrogate_pairs() {
+        let json = br#"{"type":"webauthn.get","challenge":"\ud83d\ude00","origin":"o"}"#;
+
+        assert_eq!(ClientData::parse(json).unwrap().challenge, "\u{1f600}");
+        // lone high surrogate, high surrogate followed by a non surrogate and lone low surrogate
+        assert!(!skips(r#""\ud83d""#));
+        assert!(!skips(r#""\ud83d\u0041""#));
+        assert!(!skips(r#""\ude00""#));
+    }
+
+    #[test]
+    fn rejects_duplicate_members() {
+        assert!(client_data(r#","type":"webauthn.create""#).is_none());
+        assert!(client_data(r#","origin":"https://evil.example""#).is_none());
+        // duplicates of skipped members are not interpreted
+        assert!(client_data(r#","extra":1,"extra":2"#).is_some());
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(skips(&nested(JsonParser::MAX_DEPTH as usize)));
+        assert!(!skips(&nested(JsonParser::MAX_DEPTH as usize + 1)));
+        assert!(skips(r#"{"a":[1,{"b":null}],"c":{}}"#));
+    }
+
+    #[test]
+    fn parses_numbers_and_literals() {
+        for value in [
+            "0", "-0", "12", "-0.5e+3", "1E10", "3.25", "true", "false", "null",
+        ] {
+            assert!(skips(value), "{value}");
+        }
+        for value in [
+            "1abc", "-", "01", "1.", ".5", "1e", "1e+", "+1", "--1", "tru", "nul", "nulls", "True",
+        ] {
+            assert!(!skips(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn encodes_base64url_without_padding() {
+        assert_eq!(base64url_encode(b""), "");
+        assert_eq!(base64url_encode(b"f"), "Zg");
+        assert_eq!(base64url_encode(b"fo"), "Zm8");
+        assert_eq!(base64url_encode(b"foo"), "Zm9v");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+    }
+}
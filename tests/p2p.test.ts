@@ -1,31 +1,44 @@
 import * as anchor from "@coral-xyz/anchor";
 
 import {
+  ARBITER_REGISTRY_SEED,
   DISPUTE_DEADLINE_SECS,
+  DISPUTE_EVIDENCE_SEED,
   DISPUTE_FEE_ESCROW,
+  ESCROW_SEED,
   FEE_BPS,
   FIAT_DEADLINE_SECS,
+  MERCHANT_PROFILE_SEED,
+  PAYMENT_ATTESTER_SEED,
+  USER_STATS_SEED,
 } from "./utils/constants";
 import {
   TOKEN_PROGRAM_ID,
   createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
   mintTo,
 } from "@solana/spl-token";
 import { createEventListeners, removeEventListener } from "./utils/events";
 import {
   getAllEscrowAccounts,
+  getDisputeAccount,
   getDisputeVaultAccount,
   getEscrowAccount,
   getGlobalConfigAccount,
+  getMerchantProfileAccount,
   getMintVaultAccount,
+  getUserStatsAccount,
 } from "./utils/accounts";
 
 import { P2p } from "../target/types/p2p";
 import { Program } from "@coral-xyz/anchor";
-import { bn } from "./utils/functions";
+import { bn, ethereumAddress, ethereumSign, passkeyAssertion, sha256 } from "./utils/functions";
+import { createHash, randomBytes } from "crypto";
 import { decodeUTF8 } from "tweetnacl-util";
 import { expect } from "chai";
+import { p256 } from "@noble/curves/p256";
 import nacl from "tweetnacl";
 
 describe("p2p", () => {
@@ -38,9 +51,30 @@ describe("p2p", () => {
 
   let randomMint: anchor.web3.PublicKey;
   const randomBuyer = anchor.web3.Keypair.generate();
+  // registered arbiter, never a party to the escrows (the wallet is the seller)
+  const disputeArbiter = anchor.web3.Keypair.generate();
+  const panelArbiters = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+  // disputes paid in lamports do not need the token accounts of the deposit mint
+  const lamportDeposit = { disputantAta: null, disputeVaultAta: null };
+  // dispute whose assigned arbiter let the resolution deadline pass (slashable)
+  let missedDeadlineId: number;
+  // buyer's claimed payment proof (hashes of the bank transaction id and of the receipt)
+  const paymentReference = Array.from(createHash("sha256").update("bank-tx-id").digest());
+  const receiptHash = Array.from(createHash("sha256").update("receipt").digest());
 
   const eventListeners = []; // createEventListeners(program);
 
+  // the arbiters are drawn once the scheduled slot is produced (permissionless)
+  const drawArbiters = async (id: number) => {
+    const { drawSlot } = await getDisputeAccount(program, id);
+
+    while ((await connection.getSlot()) <= drawSlot) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    return program.methods.assignArbiter(bn(id)).rpc();
+  };
+
   before(async () => {
     randomMint = await createMint(
       connection,
@@ -67,6 +101,7 @@ describe("p2p", () => {
     );
 
     await connection.requestAirdrop(randomBuyer.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await connection.requestAirdrop(disputeArbiter.publicKey, anchor.web3.LAMPORTS_PER_SOL);
   });
 
   it("`initialize`!", async () => {
@@ -83,7 +118,7 @@ describe("p2p", () => {
   it("`create_escrow`!", async () => {
     const amount = bn(10_000_000); // 10
     const tx = await program.methods
-      .createEscrow(amount)
+      .createEscrow(amount, { seller: {} })
       .accounts({
         buyer: randomBuyer.publicKey,
         mint: randomMint,
@@ -107,8 +142,8 @@ describe("p2p", () => {
   it("`mark_escrow_as_paid`!", async () => {
     const id = bn(0);
     const tx = await program.methods
-      .markEscrowAsPaid(id)
-      .accounts({ buyer: randomBuyer.publicKey })
+      .markEscrowAsPaid(id, paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
       .signers([randomBuyer])
       .rpc();
 
@@ -119,6 +154,9 @@ describe("p2p", () => {
     expect(escrowAccount.seller).to.equal(wallet.publicKey.toString());
     expect(escrowAccount.buyer).to.equal(randomBuyer.publicKey.toString());
     expect(escrowAccount.state).to.equal("fiatPaid");
+    expect(escrowAccount.paymentReference).to.equal(Buffer.from(paymentReference).toString("hex"));
+    expect(escrowAccount.receiptHash).to.equal(Buffer.from(receiptHash).toString("hex"));
+    expect(escrowAccount.buyerActed).to.equal(true);
   });
 
   it("`release_tokens_in_escrow`!", async () => {
@@ -133,7 +171,7 @@ describe("p2p", () => {
     expect(isValid).to.be.true;
 
     const tx = await program.methods
-      .releaseTokensInEscrow(bn(id), Array.from(signature))
+      .releaseTokensInEscrow(bn(id), { seller: { signature: Array.from(signature) } })
       .accounts({
         buyer: randomBuyer.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -158,7 +196,7 @@ describe("p2p", () => {
     // First, create a new escrow
     const amount = bn(15_000_000); // 15
     const createTx = await program.methods
-      .createEscrow(amount)
+      .createEscrow(amount, { seller: {} })
       .accounts({
         buyer: randomBuyer.publicKey,
         mint: randomMint,
@@ -169,6 +207,9 @@ describe("p2p", () => {
     // create a await to simulate time passing before cancelling
     await new Promise((resolve) => setTimeout(resolve, 3000));
 
+    const buyerStatsBefore = await getUserStatsAccount(program, randomBuyer.publicKey);
+    const sellerStatsBefore = await getUserStatsAccount(program, wallet.publicKey);
+
     const id = 1; // second escrow
     const tx = await program.methods
       .cancelEscrow(bn(id))
@@ -177,6 +218,12 @@ describe("p2p", () => {
 
     console.log("`cancel_escrow` tx signature:", tx);
 
+    // the buyer never acted on the escrow, its stats are not affected
+    const buyerStats = await getUserStatsAccount(program, randomBuyer.publicKey);
+    const sellerStats = await getUserStatsAccount(program, wallet.publicKey);
+    expect(buyerStats.cancellations).to.equal(buyerStatsBefore.cancellations);
+    expect(sellerStats.cancellations).to.equal(sellerStatsBefore.cancellations + 1);
+
     try {
       await getEscrowAccount(program, id);
       expect.fail("Escrow account should be closed after cancellation");
@@ -189,7 +236,7 @@ describe("p2p", () => {
     // First, create a new escrow
     const amount = bn(20_000_000); // 20
     const createTx = await program.methods
-      .createEscrow(amount)
+      .createEscrow(amount, { seller: {} })
       .accounts({
         buyer: randomBuyer.publicKey,
         mint: randomMint,
@@ -200,8 +247,8 @@ describe("p2p", () => {
     const id = 2; // third escrow
 
     const markEscrowAsPaidTx = await program.methods
-      .markEscrowAsPaid(bn(id))
-      .accounts({ buyer: randomBuyer.publicKey })
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
       .signers([randomBuyer])
       .rpc();
 
@@ -211,7 +258,11 @@ describe("p2p", () => {
     // Now, create a dispute on that escrow
     const tx = await program.methods
       .createDispute(bn(id))
-      .accounts({ disputant: randomBuyer.publicKey })
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
       .signers([randomBuyer])
       .rpc();
 
@@ -226,7 +277,10 @@ describe("p2p", () => {
     // create a await to simulate time passing before creating dispute
     await new Promise((resolve) => setTimeout(resolve, 3000));
 
-    const reTx = await program.methods.createDispute(bn(id)).rpc();
+    const reTx = await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
 
     console.log("`re-create_dispute` tx signature:", reTx);
 
@@ -235,6 +289,86 @@ describe("p2p", () => {
 
     expect(escrowAccountAfterRe.state).to.equal("reDispute");
     expect(disputeVaultAccountAfterRe?.lamports).to.greaterThan(2 * DISPUTE_FEE_ESCROW.toNumber());
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.openedBy).to.equal(randomBuyer.publicKey.toString());
+    expect(disputeAccount.buyerDeposit).to.equal(DISPUTE_FEE_ESCROW.toNumber());
+    expect(disputeAccount.sellerDeposit).to.equal(DISPUTE_FEE_ESCROW.toNumber());
+    expect(disputeAccount.redisputedAt).to.not.be.null;
+    // no arbiters registered yet, the authority resolves it
+    expect(disputeAccount.assignedArbiter).to.be.null;
+    expect(disputeAccount.outcome).to.equal("pending");
+  });
+
+  it("`submit_evidence`!", async () => {
+    const id = 2; // third escrow (in re-dispute)
+    const contentHash = createHash("sha256").update("bank statement").digest();
+    const uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    const tx = await program.methods
+      .submitEvidence(bn(id), Array.from(contentHash), uri)
+      .accounts({ submitter: randomBuyer.publicKey })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`submit_evidence` tx signature:", tx);
+
+    const [disputeEvidencePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [DISPUTE_EVIDENCE_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const disputeEvidenceAccount = await program.account.disputeEvidence.fetch(disputeEvidencePda);
+
+    expect(disputeEvidenceAccount.entries.length).to.equal(1);
+    expect(disputeEvidenceAccount.entries[0].submittedBy.toString()).to.equal(
+      randomBuyer.publicKey.toString()
+    );
+    expect(disputeEvidenceAccount.entries[0].uri).to.equal(uri);
+
+    // only the parties submit evidence
+    const outsider = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .submitEvidence(bn(id), Array.from(contentHash), uri)
+        .accounts({ submitter: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Evidence should be rejected from a non party");
+    } catch (err) {
+      expect(err.message).to.include("UnauthorizedEvidenceSubmitter");
+    }
+
+    // up to 20 entries per party
+    for (let i = 1; i < 20; i++) {
+      await program.methods
+        .submitEvidence(bn(id), Array.from(contentHash), `ipfs://${i}`)
+        .accounts({ submitter: randomBuyer.publicKey })
+        .signers([randomBuyer])
+        .rpc();
+    }
+
+    try {
+      await program.methods
+        .submitEvidence(bn(id), Array.from(contentHash), "ipfs://20")
+        .accounts({ submitter: randomBuyer.publicKey })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Evidence should be rejected above the cap");
+    } catch (err) {
+      expect(err.message).to.include("TooManyEvidenceEntries");
+    }
+
+    // the buyer's entries do not use up the seller's
+    await program.methods
+      .submitEvidence(bn(id), Array.from(contentHash), uri)
+      .accounts({ submitter: wallet.publicKey })
+      .rpc();
+
+    const disputeEvidenceAccountAfter = await program.account.disputeEvidence.fetch(
+      disputeEvidencePda
+    );
+    expect(disputeEvidenceAccountAfter.entries.length).to.equal(21);
   });
 
   it("`resolve_dispute`!", async () => {
@@ -242,19 +376,68 @@ describe("p2p", () => {
     const mintVaultAccountBefore = await getMintVaultAccount(program, randomMint);
 
     const id = 2; // third escrow
-    const to = wallet.publicKey;
-    const toSeller = to == wallet.publicKey;
 
+    // disputes are resolved by registered arbiters, not by the authority
+    const setArbiterTx = await program.methods.setArbiter(disputeArbiter.publicKey, true).rpc();
+
+    console.log("`set_arbiter` tx signature:", setArbiterTx);
+
+    // drawn from the arbiters registered by then, never the buyer or the seller
+    const assignTx = await drawArbiters(id);
+
+    console.log("`assign_arbiter` tx signature:", assignTx);
+
+    const disputeAccountBefore = await getDisputeAccount(program, id);
+    expect(disputeAccountBefore.drawSlot).to.equal(0);
+    expect(disputeAccountBefore.assignedArbiter).to.equal(disputeArbiter.publicKey.toString());
+
+    const settlement = {
+      buyer: randomBuyer.publicKey,
+      seller: wallet.publicKey,
+      disputeVaultAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // the wallet is the authority and the fallback arbiter, but also the seller
+    try {
+      await program.methods
+        .resolveDispute(bn(id), 0, false, true)
+        .accountsPartial({ settlement })
+        .rpc();
+      expect.fail("Resolve should fail when the arbiter is a party");
+    } catch (err) {
+      expect(err.message).to.include("PartyCannotArbitrate");
+    }
+
+    const outsider = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .resolveDispute(bn(id), 0, false, true)
+        .accountsPartial({ settlement: { ...settlement, signer: outsider.publicKey } })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Resolve should fail when the signer is not an arbiter");
+    } catch (err) {
+      expect(err.message).to.include("UnauthorizedArbiter");
+    }
+
+    // everything back to the seller, only the seller's deposit is refunded
     const tx = await program.methods
-      .resolveDispute(bn(id))
-      .accounts({ to, tokenProgram: TOKEN_PROGRAM_ID })
+      .resolveDispute(bn(id), 0, false, true)
+      .accountsPartial({ settlement: { ...settlement, signer: disputeArbiter.publicKey } })
+      .signers([disputeArbiter])
       .rpc();
 
     console.log("`resolve_dispute` tx signature:", tx);
 
     const globalConfigAccount = await getGlobalConfigAccount(program);
     console.log({ globalConfigAccount });
-    expect(globalConfigAccount.availableLamports).to.equal(DISPUTE_FEE_ESCROW.toNumber());
+    // the forfeited buyer deposit is shared with the arbiter (50% by default)
+    const arbiterReward = DISPUTE_FEE_ESCROW.toNumber() / 2;
+    expect(globalConfigAccount.availableLamports).to.equal(
+      DISPUTE_FEE_ESCROW.toNumber() - arbiterReward
+    );
 
     const disputeVaultAccount = await getDisputeVaultAccount(connection, program);
     console.log({ disputeVaultAccount });
@@ -268,6 +451,11 @@ describe("p2p", () => {
       mintVaultAccountBefore.availableAmount
     );
 
+    // the dispute account is kept as history
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.outcome).to.equal("resolved");
+    expect(disputeAccount.arbiter).to.equal(disputeArbiter.publicKey.toString());
+
     try {
       await getEscrowAccount(program, id);
       expect.fail("Escrow account should be closed after dispute resolution");
@@ -276,6 +464,1924 @@ describe("p2p", () => {
     }
   });
 
+  it("`resolve_dispute` with a split ruling!", async () => {
+    const amount = bn(8_000_000); // 8
+    await program.methods
+      .createEscrow(amount, { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccountBefore = await getGlobalConfigAccount(program);
+    const id = globalConfigAccountBefore.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    await drawArbiters(id);
+
+    const settlement = {
+      signer: disputeArbiter.publicKey,
+      buyer: randomBuyer.publicKey,
+      seller: wallet.publicKey,
+      disputeVaultAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods
+        .resolveDispute(bn(id), 10_001, true, true)
+        .accountsPartial({ settlement })
+        .signers([disputeArbiter])
+        .rpc();
+      expect.fail("Resolve should fail with more than 100% to the buyer");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSplitBps");
+    }
+
+    const buyerAta = getAssociatedTokenAddressSync(randomMint, randomBuyer.publicKey);
+    const sellerAta = getAssociatedTokenAddressSync(randomMint, wallet.publicKey);
+    const buyerTokensBefore = (await getAccount(connection, buyerAta)).amount;
+    const sellerTokensBefore = (await getAccount(connection, sellerAta)).amount;
+    const buyerLamportsBefore = await connection.getBalance(randomBuyer.publicKey);
+    const disputeVaultBefore = await getDisputeVaultAccount(connection, program);
+    const mintVaultAccountBefore = await getMintVaultAccount(program, randomMint);
+
+    // half of the escrow to each party, both deposits refunded
+    const tx = await program.methods
+      .resolveDispute(bn(id), 5_000, true, true)
+      .accountsPartial({ settlement })
+      .signers([disputeArbiter])
+      .rpc();
+
+    console.log("`resolve_dispute` (split) tx signature:", tx);
+
+    // the buyer pays the fee of its half, the seller gets the rest of the deposited fee back
+    const buyerShare = amount.toNumber() / 2;
+    const buyerFee = (buyerShare * FEE_BPS) / 10_000;
+    const fee = (amount.toNumber() * FEE_BPS) / 10_000;
+
+    const buyerTokensAfter = (await getAccount(connection, buyerAta)).amount;
+    const sellerTokensAfter = (await getAccount(connection, sellerAta)).amount;
+    expect(Number(buyerTokensAfter - buyerTokensBefore)).to.equal(buyerShare - buyerFee);
+    expect(Number(sellerTokensAfter - sellerTokensBefore)).to.equal(
+      amount.toNumber() - buyerShare + fee - buyerFee
+    );
+
+    const mintVaultAccount = await getMintVaultAccount(program, randomMint);
+    expect(mintVaultAccount.availableAmount - mintVaultAccountBefore.availableAmount).to.equal(
+      buyerFee
+    );
+
+    // both deposits leave the dispute vault, the buyer did not pay for this transaction
+    const disputeVaultAfter = await getDisputeVaultAccount(connection, program);
+    expect(disputeVaultBefore.lamports - disputeVaultAfter.lamports).to.equal(
+      2 * DISPUTE_FEE_ESCROW.toNumber()
+    );
+    expect((await connection.getBalance(randomBuyer.publicKey)) - buyerLamportsBefore).to.equal(
+      DISPUTE_FEE_ESCROW.toNumber()
+    );
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.outcome).to.equal("resolved");
+    expect(disputeAccount.buyerBps).to.equal(5_000);
+    expect(disputeAccount.buyerDepositRefunded).to.equal(DISPUTE_FEE_ESCROW.toNumber());
+    expect(disputeAccount.sellerDepositRefunded).to.equal(DISPUTE_FEE_ESCROW.toNumber());
+
+    // no deposit forfeited, nothing added to the protocol lamports
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    expect(globalConfigAccount.availableLamports).to.equal(
+      globalConfigAccountBefore.availableLamports
+    );
+  });
+
+  it("`vote_dispute` with an arbiter panel!", async () => {
+    for (const arbiter of panelArbiters) {
+      await connection.requestAirdrop(arbiter.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await program.methods.setArbiter(arbiter.publicKey, true).rpc();
+    }
+
+    // disputes above 10 tokens go to a panel of 3 arbiters, 2 matching votes settle them
+    await program.methods.setPanelConfig(3, 2, bn(3600)).rpc();
+    await program.methods
+      .setPanelThreshold(bn(10_000_000))
+      .accounts({ mint: randomMint })
+      .rpc();
+
+    await program.methods
+      .createEscrow(bn(20_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    await drawArbiters(id);
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.panel).to.equal(true);
+    expect(disputeAccount.votingDeadline).to.greaterThan(disputeAccount.redisputedAt);
+
+    // every registered arbiter but the parties is drawn (3 seats, 3 candidates)
+    expect(disputeAccount.panelQuorum).to.equal(2);
+    expect(disputeAccount.panelMembers).to.have.members(
+      [disputeArbiter, ...panelArbiters].map((arbiter) => arbiter.publicKey.toString())
+    );
+
+    // a later config change does not apply to this dispute
+    await program.methods.setPanelConfig(5, 3, bn(3600)).rpc();
+
+    const settlement = {
+      buyer: randomBuyer.publicKey,
+      seller: wallet.publicKey,
+      disputeVaultAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // a single arbiter cannot resolve a panel dispute
+    try {
+      await program.methods
+        .resolveDispute(bn(id), 10_000, true, false)
+        .accountsPartial({ settlement: { ...settlement, signer: disputeArbiter.publicKey } })
+        .signers([disputeArbiter])
+        .rpc();
+      expect.fail("Panel dispute should not be resolved by a single arbiter");
+    } catch (err) {
+      expect(err.message).to.include("PanelDisputeNotEscalated");
+    }
+
+    // an arbiter registered after the draw is not on the panel
+    const lateArbiter = anchor.web3.Keypair.generate();
+    await program.methods.setArbiter(lateArbiter.publicKey, true).rpc();
+
+    try {
+      await program.methods
+        .voteDispute(bn(id), 10_000, true, false)
+        .accountsPartial({ settlement: { ...settlement, signer: lateArbiter.publicKey } })
+        .signers([lateArbiter])
+        .rpc();
+      expect.fail("Vote should fail from an arbiter outside the panel");
+    } catch (err) {
+      expect(err.message).to.include("NotPanelMember");
+    }
+
+    await program.methods.setArbiter(lateArbiter.publicKey, false).rpc();
+
+    // the seller is never on its own panel
+    try {
+      await program.methods
+        .voteDispute(bn(id), 0, false, true)
+        .accountsPartial({ settlement: { ...settlement, signer: wallet.publicKey } })
+        .rpc();
+      expect.fail("Vote should fail from a party");
+    } catch (err) {
+      expect(err.message).to.include("PartyCannotArbitrate");
+    }
+
+    // everything to the buyer, only the buyer's deposit is refunded
+    for (const arbiter of panelArbiters) {
+      const tx = await program.methods
+        .voteDispute(bn(id), 10_000, true, false)
+        .accountsPartial({ settlement: { ...settlement, signer: arbiter.publicKey } })
+        .signers([arbiter])
+        .rpc();
+
+      console.log("`vote_dispute` tx signature:", tx);
+    }
+
+    const disputeAccountAfter = await getDisputeAccount(program, id);
+    expect(disputeAccountAfter.votes.length).to.equal(2);
+    expect(disputeAccountAfter.outcome).to.equal("resolved");
+
+    await program.methods.setPanelConfig(3, 2, bn(3600)).rpc();
+
+    // back to single arbiter disputes for this mint
+    await program.methods.setPanelThreshold(bn(0)).accounts({ mint: randomMint }).rpc();
+  });
+
+  it("`claim_default_judgment`!", async () => {
+    try {
+      await program.methods.updateGlobalConfig(null, null, null, null, bn(-1)).rpc();
+      expect.fail("Negative response window should be rejected");
+    } catch (err) {
+      expect(err.message).to.include("InvalidDisputeResponsePeriod");
+    }
+
+    // short response window for the test
+    await program.methods.updateGlobalConfig(null, null, null, null, bn(1)).rpc();
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    // the seller never re-disputes
+    await new Promise((resolve) => setTimeout(resolve, 4000));
+
+    const tx = await program.methods
+      .claimDefaultJudgment(bn(id))
+      .accountsPartial({
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        disputeVaultAta: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    console.log("`claim_default_judgment` tx signature:", tx);
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.outcome).to.equal("resolved");
+
+    await program.methods.updateGlobalConfig(null, null, null, null, bn(172_800)).rpc();
+  });
+
+  it("`set_dispute_policy` and a dispute from an open escrow!", async () => {
+    const window = (enabled: boolean, earliestSecs: number, latestSecs: number) => ({
+      enabled,
+      earliestSecs: bn(earliestSecs),
+      latestSecs: bn(latestSecs),
+    });
+
+    // the buyer can dispute an unpaid escrow after 1 second, within 1 hour
+    const setDisputePolicyTx = await program.methods
+      .setDisputePolicy({
+        open: window(true, 1, 3600),
+        fiatPaid: window(true, DISPUTE_DEADLINE_SECS.toNumber(), 0),
+        reDispute: window(true, DISPUTE_DEADLINE_SECS.toNumber(), 0),
+      })
+      .rpc();
+
+    console.log("`set_dispute_policy` tx signature:", setDisputePolicyTx);
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    expect(globalConfigAccount.openDisputesEnabled).to.equal(true);
+
+    // the buyer bond is posted with the dispute, the escrow was never marked as paid
+    const bond = 0.05 * anchor.web3.LAMPORTS_PER_SOL;
+    await program.methods
+      .setBuyerBond(bn(bond), true, 1_000)
+      .accounts({ mint: randomMint })
+      .rpc();
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const id = globalConfigAccount.escrowCount;
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const disputeVaultBefore = await getDisputeVaultAccount(connection, program);
+
+    // never marked as paid
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.state).to.equal("dispute");
+    expect(escrowAccount.buyerBond).to.equal(bond);
+
+    const disputeVault = await getDisputeVaultAccount(connection, program);
+    expect(disputeVault.lamports).to.equal(
+      disputeVaultBefore.lamports + DISPUTE_FEE_ESCROW.toNumber() + bond
+    );
+
+    await program.methods.setBuyerBond(bn(0), false, 0).accounts({ mint: randomMint }).rpc();
+
+    await program.methods
+      .setDisputePolicy({
+        open: window(false, 0, 0),
+        fiatPaid: window(true, DISPUTE_DEADLINE_SECS.toNumber(), 0),
+        reDispute: window(true, DISPUTE_DEADLINE_SECS.toNumber(), 0),
+      })
+      .rpc();
+  });
+
+  it("`execute_default_ruling` after the resolution deadline!", async () => {
+    // 1 second SLA, the default ruling splits the escrow in half and keeps both deposits
+    const defaultRuling = { buyerBps: 5_000, refundBuyerDeposit: false, refundSellerDeposit: false };
+
+    try {
+      await program.methods.setResolutionPolicy(bn(0), wallet.publicKey, defaultRuling).rpc();
+      expect.fail("Set resolution policy should fail without an SLA");
+    } catch (err) {
+      expect(err.message).to.include("InvalidResolutionPolicy");
+    }
+
+    await program.methods.setResolutionPolicy(bn(1), wallet.publicKey, defaultRuling).rpc();
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    await drawArbiters(id);
+
+    // the assigned arbiter does not resolve it in time
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const tx = await program.methods
+      .executeDefaultRuling(bn(id))
+      .accountsPartial({
+        settlement: {
+          signer: randomBuyer.publicKey,
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          disputeVaultAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`execute_default_ruling` tx signature:", tx);
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.outcome).to.equal("resolved");
+    expect(disputeAccount.arbiter).to.be.null;
+    expect(disputeAccount.assignedArbiter).to.not.be.null;
+
+    missedDeadlineId = id;
+
+    await program.methods
+      .setResolutionPolicy(bn(259_200), wallet.publicKey, {
+        buyerBps: 0,
+        refundBuyerDeposit: true,
+        refundSellerDeposit: true,
+      })
+      .rpc();
+  });
+
+  it("`set_dispute_bond` and a dispute bond in the escrow mint!", async () => {
+    // 5% of the escrow amount, at least 0.1 token, no cap
+    const setDisputeBondTx = await program.methods
+      .setDisputeBond(500, bn(100_000), bn(0))
+      .accounts({ mint: randomMint })
+      .rpc();
+
+    console.log("`set_dispute_bond` tx signature:", setDisputeBondTx);
+
+    await program.methods
+      .createEscrow(bn(20_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accounts({ disputant: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accounts({ tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    const bond = 1_000_000; // 5% of 20 tokens
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.depositMint).to.equal(randomMint.toString());
+    expect(disputeAccount.buyerDeposit).to.equal(bond);
+    expect(disputeAccount.sellerDeposit).to.equal(bond);
+
+    const mintVaultAccountBefore = await getMintVaultAccount(program, randomMint);
+
+    await drawArbiters(id);
+
+    const { assignedArbiter } = await getDisputeAccount(program, id);
+    const assigned = [disputeArbiter, ...panelArbiters].find(
+      (arbiter) => arbiter.publicKey.toString() === assignedArbiter
+    );
+
+    // everything to the seller, the buyer bond is forfeited
+    await program.methods
+      .resolveDispute(bn(id), 0, false, true)
+      .accounts({
+        settlement: {
+          signer: assigned.publicKey,
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .signers([assigned])
+      .rpc();
+
+    // half of the forfeited bond rewards the arbiter, the rest goes to the mint vault
+    const arbiterReward = bond / 2;
+    const mintVaultAccount = await getMintVaultAccount(program, randomMint);
+    expect(mintVaultAccount.availableAmount).to.greaterThanOrEqual(
+      mintVaultAccountBefore.availableAmount + bond - arbiterReward
+    );
+    expect(
+      mintVaultAccount.arbiterRewards.find((reward) => reward.arbiter === assignedArbiter).amount
+    ).to.equal(arbiterReward);
+
+    const assignedAta = getAssociatedTokenAddressSync(randomMint, assigned.publicKey);
+
+    const claimTx = await program.methods
+      .claimArbiterTokenRewards()
+      .accounts({ arbiter: assigned.publicKey, mint: randomMint, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([assigned])
+      .rpc();
+
+    console.log("`claim_arbiter_token_rewards` tx signature:", claimTx);
+
+    const assignedAtaAccount = await getAccount(connection, assignedAta);
+    expect(Number(assignedAtaAccount.amount)).to.equal(arbiterReward);
+
+    try {
+      await program.methods
+        .claimArbiterTokenRewards()
+        .accounts({
+          arbiter: assigned.publicKey,
+          mint: randomMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([assigned])
+        .rpc();
+      expect.fail("Claim should fail without token rewards");
+    } catch (err) {
+      expect(err.message).to.include("NoArbiterRewards");
+    }
+
+    await program.methods.setDisputeBond(0, bn(0), bn(0)).accounts({ mint: randomMint }).rpc();
+  });
+
+  it("`appeal_ruling` escalates the ruling to a panel!", async () => {
+    // appealing must cost more than the appellant's dispute deposit
+    try {
+      await program.methods.setAppealPolicy(bn(60), 10_000).rpc();
+      expect.fail("Set appeal policy should fail with a bond not above the deposit");
+    } catch (err) {
+      expect(err.message).to.include("InvalidAppealConfig");
+    }
+
+    // twice the appellant's dispute deposit, in the same mint
+    await program.methods.setAppealPolicy(bn(60), 20_000).rpc();
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    const settlement = {
+      buyer: randomBuyer.publicKey,
+      seller: wallet.publicKey,
+      disputeVaultAta: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // nobody but the fallback arbiter (after the deadline) can resolve before the draw
+    try {
+      await program.methods
+        .resolveDispute(bn(id), 0, false, true)
+        .accountsPartial({ settlement: { ...settlement, signer: disputeArbiter.publicKey } })
+        .signers([disputeArbiter])
+        .rpc();
+      expect.fail("Resolve should fail before the arbiter is drawn");
+    } catch (err) {
+      expect(err.message).to.include("UnauthorizedArbiter");
+    }
+
+    await drawArbiters(id);
+
+    // the parties are never assigned, so it is one of the registered arbiters
+    const disputeAccount = await getDisputeAccount(program, id);
+    const registered = [disputeArbiter, ...panelArbiters];
+    const assigned = registered.find(
+      (arbiter) => arbiter.publicKey.toString() === disputeAccount.assignedArbiter
+    );
+
+    // the assigned arbiter is the only registered arbiter that can resolve
+    for (const other of registered.filter((arbiter) => arbiter !== assigned)) {
+      try {
+        await program.methods
+          .resolveDispute(bn(id), 0, false, true)
+          .accountsPartial({ settlement: { ...settlement, signer: other.publicKey } })
+          .signers([other])
+          .rpc();
+        expect.fail("Resolve should fail from an arbiter that was not assigned");
+      } catch (err) {
+        expect(err.message).to.include("UnauthorizedArbiter");
+      }
+    }
+
+    // everything to the seller, recorded but not settled
+    await program.methods
+      .resolveDispute(bn(id), 0, false, true)
+      .accountsPartial({ settlement: { ...settlement, signer: assigned.publicKey } })
+      .signers([assigned])
+      .rpc();
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.state).to.equal("reDispute");
+
+    const tx = await program.methods
+      .appealRuling(bn(id))
+      .accountsPartial({
+        appellant: randomBuyer.publicKey,
+        mint: randomMint,
+        appellantAta: null,
+        disputeVaultAta: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`appeal_ruling` tx signature:", tx);
+
+    await drawArbiters(id);
+
+    const disputeAccountAfter = await getDisputeAccount(program, id);
+    expect(disputeAccountAfter.appellant).to.equal(randomBuyer.publicKey.toString());
+    expect(disputeAccountAfter.appealBond).to.equal(2 * disputeAccountAfter.buyerDeposit);
+    expect(disputeAccountAfter.panel).to.equal(true);
+    // the appeal panel never includes the arbiter of the appealed ruling
+    expect(disputeAccountAfter.panelMembers).to.not.be.empty;
+    expect(disputeAccountAfter.panelMembers).to.not.include(assigned.publicKey.toString());
+    expect(disputeAccountAfter.outcome).to.equal("pending");
+
+    await program.methods.setAppealPolicy(bn(0), 0).rpc();
+  });
+
+  it("arbiter staking, rewards and slashing!", async () => {
+    // rewards from the resolved disputes
+    const claimTx = await program.methods
+      .claimArbiterRewards()
+      .accounts({ arbiter: disputeArbiter.publicKey })
+      .signers([disputeArbiter])
+      .rpc();
+
+    console.log("`claim_arbiter_rewards` tx signature:", claimTx);
+
+    const arbiter = anchor.web3.Keypair.generate();
+    await connection.requestAirdrop(arbiter.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.methods.setArbiter(arbiter.publicKey, true).rpc();
+
+    const stake = bn(anchor.web3.LAMPORTS_PER_SOL);
+    const stakeTx = await program.methods
+      .stakeArbiter(stake)
+      .accounts({ arbiter: arbiter.publicKey })
+      .signers([arbiter])
+      .rpc();
+
+    console.log("`stake_arbiter` tx signature:", stakeTx);
+
+    const lamports = bn(0.25 * anchor.web3.LAMPORTS_PER_SOL);
+
+    // the new arbiter was not drawn for the dispute
+    try {
+      await program.methods
+        .slashArbiter(bn(missedDeadlineId), arbiter.publicKey, lamports)
+        .rpc();
+      expect.fail("Slash should fail for an arbiter that did not miss its deadline");
+    } catch (err) {
+      expect(err.message).to.include("ArbiterNotSlashable");
+    }
+
+    // the assigned arbiter let the resolution deadline pass
+    const { assignedArbiter } = await getDisputeAccount(program, missedDeadlineId);
+    const missed = [disputeArbiter, ...panelArbiters].find(
+      (entry) => entry.publicKey.toString() === assignedArbiter
+    );
+
+    await program.methods
+      .stakeArbiter(bn(0.5 * anchor.web3.LAMPORTS_PER_SOL))
+      .accounts({ arbiter: missed.publicKey })
+      .signers([missed])
+      .rpc();
+
+    const slashTx = await program.methods
+      .slashArbiter(bn(missedDeadlineId), missed.publicKey, lamports)
+      .rpc();
+
+    console.log("`slash_arbiter` tx signature:", slashTx);
+
+    const disputeAccount = await getDisputeAccount(program, missedDeadlineId);
+    expect(disputeAccount.slashedArbiters).to.deep.equal([assignedArbiter]);
+
+    // once per dispute
+    try {
+      await program.methods
+        .slashArbiter(bn(missedDeadlineId), missed.publicKey, lamports)
+        .rpc();
+      expect.fail("Slash should fail twice for the same dispute");
+    } catch (err) {
+      expect(err.message).to.include("ArbiterNotSlashable");
+    }
+
+    // starts the unbonding, the stake can be withdrawn after ARBITER_UNBONDING_SECS
+    await program.methods
+      .unstakeArbiter()
+      .accounts({ arbiter: arbiter.publicKey })
+      .signers([arbiter])
+      .rpc();
+
+    const [arbiterRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ARBITER_REGISTRY_SEED],
+      program.programId
+    );
+    const arbiterRegistryAccount = await program.account.arbiterRegistry.fetch(arbiterRegistryPda);
+    const disputeArbiterEntry = arbiterRegistryAccount.arbiters.find((entry) =>
+      entry.key.equals(disputeArbiter.publicKey)
+    );
+    const arbiterEntry = arbiterRegistryAccount.arbiters.find((entry) =>
+      entry.key.equals(arbiter.publicKey)
+    );
+    const missedEntry = arbiterRegistryAccount.arbiters.find((entry) =>
+      entry.key.equals(missed.publicKey)
+    );
+
+    expect(disputeArbiterEntry.rewards.toNumber()).to.equal(0);
+    expect(disputeArbiterEntry.resolvedCount).to.greaterThan(0);
+    expect(arbiterEntry.stake.toNumber()).to.equal(anchor.web3.LAMPORTS_PER_SOL);
+    expect(missedEntry.stake.toNumber()).to.equal(0.25 * anchor.web3.LAMPORTS_PER_SOL);
+    expect(arbiterEntry.unbondingUntil.toNumber()).to.greaterThan(0);
+
+    try {
+      await program.methods
+        .unstakeArbiter()
+        .accounts({ arbiter: arbiter.publicKey })
+        .signers([arbiter])
+        .rpc();
+      expect.fail("Unstake should fail before the unbonding period is over");
+    } catch (err) {
+      expect(err.message).to.include("UnbondingNotFinished");
+    }
+  });
+
+  it("`freeze_escrow` and `force_release`!", async () => {
+    // frozen by one arbiter, force settled by another
+    const [frozenBy, arbiter] = panelArbiters;
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    const paidEscrowAccount = await getEscrowAccount(program, id);
+
+    // not disputed, no dispute account
+    const reason = 1; // stolen card
+    const freeze = () =>
+      program.methods
+        .freezeEscrow(bn(id), reason)
+        .accountsPartial({ arbiter: frozenBy.publicKey, dispute: null })
+        .signers([frozenBy])
+        .rpc();
+
+    const freezeTx = await freeze();
+
+    console.log("`freeze_escrow` tx signature:", freezeTx);
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.state).to.equal("frozen");
+    expect(escrowAccount.frozenBy).to.equal(frozenBy.publicKey.toString());
+    expect(escrowAccount.priorState).to.equal("fiatPaid");
+
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    // the authority can restore the escrow
+    const unfreezeTx = await program.methods
+      .unfreezeEscrow(bn(id))
+      .accountsPartial({ dispute: null })
+      .rpc();
+
+    console.log("`unfreeze_escrow` tx signature:", unfreezeTx);
+
+    // the time frozen does not count for the deadlines
+    const unfrozenEscrowAccount = await getEscrowAccount(program, id);
+    expect(unfrozenEscrowAccount.state).to.equal("fiatPaid");
+    expect(unfrozenEscrowAccount.frozenBy).to.be.null;
+    expect(unfrozenEscrowAccount.timestamp).to.greaterThan(paidEscrowAccount.timestamp);
+    expect(unfrozenEscrowAccount.fiatDeadline - paidEscrowAccount.fiatDeadline).to.equal(
+      unfrozenEscrowAccount.timestamp - paidEscrowAccount.timestamp
+    );
+
+    await freeze();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    try {
+      await program.methods
+        .createDispute(bn(id))
+        .accountsPartial({
+          disputant: randomBuyer.publicKey,
+          ...lamportDeposit,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("A frozen escrow cannot be disputed");
+    } catch (err) {
+      expect(err.message).to.include("EscrowFrozen");
+    }
+
+    // never disputed, no dispute account
+    const forceRelease = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .forceRelease(bn(id), reason)
+        .accountsPartial({
+          arbiter: signer.publicKey,
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          dispute: null,
+          disputeVaultAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+
+    try {
+      await forceRelease(frozenBy);
+      expect.fail("Force release should fail from the arbiter that froze the escrow");
+    } catch (err) {
+      expect(err.message).to.include("FreezeNotApproved");
+    }
+
+    const tx = await forceRelease(arbiter);
+
+    console.log("`force_release` tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after a forced release");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("`force_cancel` of a frozen escrow!", async () => {
+    const [frozenBy, arbiter] = panelArbiters;
+    const walletAta = getAssociatedTokenAddressSync(randomMint, wallet.publicKey);
+    const walletAtaBefore = await getAccount(connection, walletAta);
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const reason = 2; // fake buyer
+    await program.methods
+      .freezeEscrow(bn(id), reason)
+      .accountsPartial({ arbiter: frozenBy.publicKey, dispute: null })
+      .signers([frozenBy])
+      .rpc();
+
+    // no buyer bond was posted, nothing to forfeit
+    const tx = await program.methods
+      .forceCancel(bn(id), reason, false)
+      .accountsPartial({
+        arbiter: arbiter.publicKey,
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        dispute: null,
+        disputeVaultAta: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([arbiter])
+      .rpc();
+
+    console.log("`force_cancel` tx signature:", tx);
+
+    // the seller gets the amount and the deposited fee back
+    const walletAtaAfter = await getAccount(connection, walletAta);
+    expect(walletAtaAfter.amount).to.equal(walletAtaBefore.amount);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after a forced cancel");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("`mutual_cancel` after the escrow is paid!", async () => {
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    // the buyer approves off-chain, the seller signs the transaction
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const message = decodeUTF8(`approve_cancel:${escrowPda.toString()}`);
+    const buyerSignature = nacl.sign.detached(message, randomBuyer.secretKey);
+
+    const tx = await program.methods
+      .mutualCancel(bn(id), Array.from(buyerSignature), null)
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    console.log("`mutual_cancel` tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after a mutual cancel");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("`set_buyer_bond` and a buyer bond returned on release!", async () => {
+    // 0.05 SOL, 10% protocol cut if forfeited
+    const bond = 0.05 * anchor.web3.LAMPORTS_PER_SOL;
+    const setBuyerBondTx = await program.methods
+      .setBuyerBond(bn(bond), true, 1_000)
+      .accounts({ mint: randomMint })
+      .rpc();
+
+    console.log("`set_buyer_bond` tx signature:", setBuyerBondTx);
+
+    const mintVaultAccount = await getMintVaultAccount(program, randomMint);
+    expect(mintVaultAccount.buyerBond).to.equal(bond);
+    expect(mintVaultAccount.buyerBondInLamports).to.be.true;
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const disputeVaultBefore = await getDisputeVaultAccount(connection, program);
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    // the bond is held in the dispute vault
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.buyerBond).to.equal(bond);
+    expect(escrowAccount.buyerBondInLamports).to.be.true;
+
+    const disputeVault = await getDisputeVaultAccount(connection, program);
+    expect(disputeVault.lamports).to.equal(disputeVaultBefore.lamports + bond);
+
+    // the release returns it to the buyer
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const message = decodeUTF8(`approve_release:${escrowPda.toString()}`);
+    const signature = nacl.sign.detached(message, wallet.payer.secretKey);
+
+    await program.methods
+      .releaseTokensInEscrow(bn(id), { seller: { signature: Array.from(signature) } })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    const disputeVaultAfter = await getDisputeVaultAccount(connection, program);
+    expect(disputeVaultAfter.lamports).to.equal(disputeVaultBefore.lamports);
+
+    await program.methods.setBuyerBond(bn(0), false, 0).accounts({ mint: randomMint }).rpc();
+  });
+
+  it("verified merchant bond, fee and escrow limits!", async () => {
+    // 0.1 SOL bond to be verified, 0.5% fee
+    const minBond = 0.1 * anchor.web3.LAMPORTS_PER_SOL;
+    await program.methods.setMerchantConfig(bn(minBond), 50).rpc();
+
+    // 10 tokens limit, 50 for verified merchants
+    await program.methods
+      .setEscrowLimits(bn(10_000_000), bn(50_000_000))
+      .accounts({ mint: randomMint })
+      .rpc();
+
+    const stakeTx = await program.methods.stakeMerchantBond(bn(minBond)).rpc();
+
+    console.log("`stake_merchant_bond` tx signature:", stakeTx);
+
+    const merchantProfile = await getMerchantProfileAccount(program, wallet.publicKey);
+    expect(merchantProfile.bond).to.equal(minBond);
+
+    const [merchantProfilePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [MERCHANT_PROFILE_SEED, wallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // without the profile the standard limit applies
+    try {
+      await program.methods
+        .createEscrow(bn(20_000_000), { seller: {} })
+        .accountsPartial({
+          buyer: randomBuyer.publicKey,
+          mint: randomMint,
+          merchantProfile: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("The escrow should exceed the mint limit");
+    } catch (err) {
+      expect(err.message).to.include("EscrowLimitExceeded");
+    }
+
+    await program.methods
+      .createEscrow(bn(20_000_000), { seller: {} })
+      .accountsPartial({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        merchantProfile: merchantProfilePda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.feeBps).to.equal(50);
+    expect(escrowAccount.verifiedMerchant).to.equal(true);
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({
+        disputant: randomBuyer.publicKey,
+        ...lamportDeposit,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    // the bond backs the dispute until it is settled
+    const disputedProfile = await getMerchantProfileAccount(program, wallet.publicKey);
+    expect(disputedProfile.pendingDisputes).to.equal(1);
+
+    try {
+      await program.methods.unstakeMerchantBond().rpc();
+      expect.fail("Unstake should fail while a dispute is pending");
+    } catch (err) {
+      expect(err.message).to.include("MerchantDisputePending");
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    await program.methods
+      .createDispute(bn(id))
+      .accountsPartial({ ...lamportDeposit, tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    await drawArbiters(id);
+
+    const { assignedArbiter } = await getDisputeAccount(program, id);
+    const arbiters = [disputeArbiter, ...panelArbiters];
+    const ruling = arbiters.find((entry) => entry.publicKey.toString() === assignedArbiter);
+    const other = arbiters.find((entry) => entry !== ruling);
+
+    // everything to the buyer
+    await program.methods
+      .resolveDispute(bn(id), 10_000, true, false)
+      .accountsPartial({
+        settlement: {
+          signer: ruling.publicKey,
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          disputeVaultAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+      .signers([ruling])
+      .rpc();
+
+    const resolvedProfile = await getMerchantProfileAccount(program, wallet.publicKey);
+    expect(resolvedProfile.pendingDisputes).to.equal(0);
+
+    // only the arbiter that ruled against the merchant slashes its bond
+    try {
+      await program.methods
+        .slashMerchantBond(bn(id), bn(minBond))
+        .accounts({ arbiter: other.publicKey, buyer: randomBuyer.publicKey })
+        .signers([other])
+        .rpc();
+      expect.fail("Slash should fail from an arbiter that did not rule");
+    } catch (err) {
+      expect(err.message).to.include("MerchantNotSlashable");
+    }
+
+    const slashTx = await program.methods
+      .slashMerchantBond(bn(id), bn(minBond))
+      .accounts({ arbiter: ruling.publicKey, buyer: randomBuyer.publicKey })
+      .signers([ruling])
+      .rpc();
+
+    console.log("`slash_merchant_bond` tx signature:", slashTx);
+
+    // capped at the escrow amount
+    const slashedProfile = await getMerchantProfileAccount(program, wallet.publicKey);
+    expect(slashedProfile.slashed).to.equal(20_000_000);
+    expect(slashedProfile.bond).to.equal(minBond - 20_000_000);
+
+    const disputeAccount = await getDisputeAccount(program, id);
+    expect(disputeAccount.merchantSlashed).to.equal(20_000_000);
+
+    // once per dispute
+    try {
+      await program.methods
+        .slashMerchantBond(bn(id), bn(minBond))
+        .accounts({ arbiter: ruling.publicKey, buyer: randomBuyer.publicKey })
+        .signers([ruling])
+        .rpc();
+      expect.fail("Slash should fail twice for the same dispute");
+    } catch (err) {
+      expect(err.message).to.include("MerchantNotSlashable");
+    }
+
+    // the unbonding removes the verified status
+    await program.methods.unstakeMerchantBond().rpc();
+
+    const unbondingProfile = await getMerchantProfileAccount(program, wallet.publicKey);
+    expect(unbondingProfile.unbondingUntil).to.greaterThan(0);
+
+    await program.methods.setEscrowLimits(bn(0), bn(0)).accounts({ mint: randomMint }).rpc();
+    await program.methods.setMerchantConfig(bn(0), FEE_BPS).rpc();
+  });
+
+  it("`buyer_abandon` before paying!", async () => {
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    let globalConfigAccount = await getGlobalConfigAccount(program);
+    let id = globalConfigAccount.escrowCount - 1;
+
+    // only the buyer abandons
+    try {
+      await program.methods
+        .buyerAbandon(bn(id))
+        .accounts({
+          buyer: wallet.publicKey,
+          seller: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("The seller should not abandon the escrow");
+    } catch (err) {
+      expect(err.message).to.include("ConstraintHasOne");
+    }
+
+    const abandonsBefore = await getUserStatsAccount(program, randomBuyer.publicKey)
+      .then((stats) => stats.abandons)
+      .catch(() => 0);
+
+    const tx = await program.methods
+      .buyerAbandon(bn(id))
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`buyer_abandon` tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after the buyer abandons");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+
+    const buyerStats = await getUserStatsAccount(program, randomBuyer.publicKey);
+    expect(buyerStats.abandons).to.equal(abandonsBefore + 1);
+
+    // not once marked as paid
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    globalConfigAccount = await getGlobalConfigAccount(program);
+    id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    try {
+      await program.methods
+        .buyerAbandon(bn(id))
+        .accounts({
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("A paid escrow should not be abandoned");
+    } catch (err) {
+      expect(err.message).to.include("InvalidEscrowState");
+    }
+  });
+
+  it("`extend_fiat_deadline` after the payment window!", async () => {
+    try {
+      await program.methods.setFiatGracePeriod(bn(-1)).rpc();
+      expect.fail("Negative grace period should be rejected");
+    } catch (err) {
+      expect(err.message).to.include("InvalidFiatGracePeriod");
+    }
+
+    await program.methods
+      .createEscrow(bn(5_000_000), { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    try {
+      await program.methods
+        .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+        .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("The escrow cannot be marked as paid after the fiat deadline");
+    } catch (err) {
+      expect(err.message).to.include("FiatDeadlinePassed");
+    }
+
+    const escrowAccountBefore = await getEscrowAccount(program, id);
+
+    // positive and at most 7 days per extension
+    for (const extensionSecs of [0, 604_801]) {
+      try {
+        await program.methods.extendFiatDeadline(bn(id), bn(extensionSecs)).rpc();
+        expect.fail("The extension should be rejected");
+      } catch (err) {
+        expect(err.message).to.include("InvalidFiatDeadlineExtension");
+      }
+    }
+
+    const tx = await program.methods.extendFiatDeadline(bn(id), bn(600)).rpc();
+
+    console.log("`extend_fiat_deadline` tx signature:", tx);
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.fiatDeadline).to.equal(escrowAccountBefore.fiatDeadline + 600);
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    const escrowAccountAfter = await getEscrowAccount(program, id);
+    expect(escrowAccountAfter.state).to.equal("fiatPaid");
+  });
+
+  it("`set_signer_set` and release with M of N approvals!", async () => {
+    const operators = [
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+      anchor.web3.Keypair.generate(),
+    ];
+
+    const setTx = await program.methods
+      .setSignerSet(2, operators.map((operator) => operator.publicKey))
+      .rpc();
+
+    console.log("`set_signer_set` tx signature:", setTx);
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, { signerSet: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    // the escrow keeps a copy of the signer set it was created with
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.signerSetThreshold).to.equal(2);
+    expect(escrowAccount.signerSetSigners).to.deep.equal(
+      operators.map((operator) => operator.publicKey.toString())
+    );
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const messageBytes = decodeUTF8(`approve_release:${escrowPda.toString()}`);
+
+    // the owner alone replaces the set, the existing escrow still needs 2 of the 3 operators
+    await program.methods.setSignerSet(1, [wallet.publicKey]).rpc();
+
+    const ownerApproval = [
+      {
+        signerIndex: 0,
+        signature: Array.from(nacl.sign.detached(messageBytes, wallet.payer.secretKey)),
+      },
+    ];
+
+    try {
+      await program.methods
+        .releaseTokensInEscrow(bn(id), { signerSet: { approvals: ownerApproval } })
+        .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Release should fail with the replaced signer set");
+    } catch (err) {
+      expect(err.message).to.include("SignatureVerificationFailed");
+    }
+
+    // a single approval is not enough
+    const singleApproval = [
+      {
+        signerIndex: 0,
+        signature: Array.from(nacl.sign.detached(messageBytes, operators[0].secretKey)),
+      },
+    ];
+
+    try {
+      await program.methods
+        .releaseTokensInEscrow(bn(id), { signerSet: { approvals: singleApproval } })
+        .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Release should fail with a single approval");
+    } catch (err) {
+      expect(err.message).to.include("NotEnoughApprovals");
+    }
+
+    const approvals = [0, 2].map((signerIndex) => ({
+      signerIndex,
+      signature: Array.from(nacl.sign.detached(messageBytes, operators[signerIndex].secretKey)),
+    }));
+
+    const tx = await program.methods
+      .releaseTokensInEscrow(bn(id), { signerSet: { approvals } })
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`release_tokens_in_escrow` (signer set) tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after token release");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("release approved by an Ethereum signature!", async () => {
+    const ethereumKey = randomBytes(32);
+    const address = ethereumAddress(ethereumKey);
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, { ethereum: { address } })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.releaseApprover).to.equal("ethereum");
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const messageBytes = decodeUTF8(`approve_release:${escrowPda.toString()}`);
+
+    // signed by another Ethereum key
+    const otherApproval = ethereumSign(messageBytes, randomBytes(32));
+
+    try {
+      await program.methods
+        .releaseTokensInEscrow(bn(id), { ethereum: otherApproval })
+        .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Release should fail with a signature from another address");
+    } catch (err) {
+      expect(err.message).to.include("SignatureVerificationFailed");
+    }
+
+    // signed by the escrow's key, but over another escrow's message
+    const [otherEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id + 1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const tamperedApproval = ethereumSign(
+      decodeUTF8(`approve_release:${otherEscrowPda.toString()}`),
+      ethereumKey
+    );
+
+    try {
+      await program.methods
+        .releaseTokensInEscrow(bn(id), { ethereum: tamperedApproval })
+        .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Release should fail with a signature over another message");
+    } catch (err) {
+      expect(err.message).to.include("SignatureVerificationFailed");
+    }
+
+    const tx = await program.methods
+      .releaseTokensInEscrow(bn(id), { ethereum: ethereumSign(messageBytes, ethereumKey) })
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    console.log("`release_tokens_in_escrow` (ethereum) tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after token release");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("release approved by a passkey!", async () => {
+    const passkeyKey = p256.utils.randomPrivateKey();
+    const rpId = "p2p.example";
+    const origin = "https://p2p.example";
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, {
+        passkey: {
+          pubkey: Array.from(p256.getPublicKey(passkeyKey, true)),
+          rpIdHash: Array.from(sha256(rpId)),
+          originHash: Array.from(sha256(origin)),
+        },
+      })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    await program.methods
+      .markEscrowAsPaid(bn(id), paymentReference, receiptHash)
+      .accounts({ buyer: randomBuyer.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([randomBuyer])
+      .rpc();
+
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const messageBytes = decodeUTF8(`approve_release:${escrowPda.toString()}`);
+    const challenge = sha256(messageBytes).toString("base64url");
+
+    const release = ({ approval, verifyInstruction }: ReturnType<typeof passkeyAssertion>) =>
+      program.methods
+        .releaseTokensInEscrow(bn(id), approval)
+        .accounts({
+          buyer: randomBuyer.publicKey,
+          instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .preInstructions([verifyInstruction])
+        .signers([randomBuyer])
+        .rpc();
+
+    // signed by the passkey, but the challenge only appears in a nested member
+    const nestedChallenge = JSON.stringify({
+      type: "webauthn.get",
+      challenge: "not-the-release-message",
+      origin,
+      extra: { challenge },
+    });
+
+    try {
+      await release(
+        passkeyAssertion(messageBytes, passkeyKey, { rpId, origin, clientDataJson: nestedChallenge })
+      );
+      expect.fail("Release should fail with a nested challenge");
+    } catch (err) {
+      expect(err.message).to.include("InvalidWebAuthnAssertion");
+    }
+
+    // assertion for another relying party
+    try {
+      await release(passkeyAssertion(messageBytes, passkeyKey, { rpId: "evil.example", origin }));
+      expect.fail("Release should fail with another rp id");
+    } catch (err) {
+      expect(err.message).to.include("InvalidWebAuthnAssertion");
+    }
+
+    // assertion from another origin
+    try {
+      await release(passkeyAssertion(messageBytes, passkeyKey, { rpId, origin: "https://evil.example" }));
+      expect.fail("Release should fail with another origin");
+    } catch (err) {
+      expect(err.message).to.include("InvalidWebAuthnAssertion");
+    }
+
+    const tx = await release(passkeyAssertion(messageBytes, passkeyKey, { rpId, origin }));
+
+    console.log("`release_tokens_in_escrow` (passkey) tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after token release");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("`claim_with_preimage`!", async () => {
+    const preimage = randomBytes(32);
+    const hash = createHash("sha256").update(preimage).digest();
+    const expiresAt = bn(Math.floor(Date.now() / 1000) + 600); // 10 minutes
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, { hashLock: { hash: Array.from(hash), expiresAt } })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const escrowAccount = await getEscrowAccount(program, id);
+    expect(escrowAccount.releaseApprover).to.equal("hashLock");
+
+    // hash locked escrows cannot be disputed
+    try {
+      await program.methods
+        .createDispute(bn(id))
+        .accountsPartial({
+          disputant: randomBuyer.publicKey,
+          ...lamportDeposit,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([randomBuyer])
+        .rpc();
+      expect.fail("Dispute should fail on a hash locked escrow");
+    } catch (err) {
+      expect(err.message).to.include("CannotDisputeEscrow");
+    }
+
+    try {
+      await program.methods
+        .claimWithPreimage(bn(id), Array.from(randomBytes(32)))
+        .accounts({
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Claim should fail with a wrong preimage");
+    } catch (err) {
+      expect(err.message).to.include("CannotClaimWithPreimage");
+    }
+
+    // anyone can claim with the preimage (the wallet pays for the buyer)
+    const tx = await program.methods
+      .claimWithPreimage(bn(id), Array.from(preimage))
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    console.log("`claim_with_preimage` tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after the claim");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("hash locked escrow refunded to the seller after expiry!", async () => {
+    const preimage = randomBytes(32);
+    const hash = createHash("sha256").update(preimage).digest();
+    const expiresAt = bn(Math.floor(Date.now() / 1000) + 2);
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, { hashLock: { hash: Array.from(hash), expiresAt } })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    // the seller cannot cancel before the lock expires
+    try {
+      await program.methods
+        .cancelEscrow(bn(id))
+        .accounts({ tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+      expect.fail("Cancel should fail before the lock expires");
+    } catch (err) {
+      expect(err.message).to.include("CannotCancelEscrow");
+    }
+
+    // create a await to let the lock expire
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    // the preimage is no longer accepted
+    try {
+      await program.methods
+        .claimWithPreimage(bn(id), Array.from(preimage))
+        .accounts({
+          buyer: randomBuyer.publicKey,
+          seller: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Claim should fail after the lock expires");
+    } catch (err) {
+      expect(err.message).to.include("CannotClaimWithPreimage");
+    }
+
+    const tx = await program.methods
+      .cancelEscrow(bn(id))
+      .accounts({ tokenProgram: TOKEN_PROGRAM_ID })
+      .rpc();
+
+    console.log("`cancel_escrow` (expired hash lock) tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after the refund");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("`set_payment_attester` and `attested_release`!", async () => {
+    const attester = anchor.web3.Keypair.generate();
+
+    const setTx = await program.methods.setPaymentAttester(attester.publicKey, true).rpc();
+
+    console.log("`set_payment_attester` tx signature:", setTx);
+
+    const amount = bn(5_000_000); // 5
+    await program.methods
+      .createEscrow(amount, { seller: {} })
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        mint: randomMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const globalConfigAccount = await getGlobalConfigAccount(program);
+    const id = globalConfigAccount.escrowCount - 1;
+
+    const [escrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, bn(id).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [paymentAttesterPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [PAYMENT_ATTESTER_SEED, attester.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // the attester confirms the fiat transfer off-chain
+    const fiatAmount = bn(10_000);
+    const reference = createHash("sha256").update("bank-transfer-reference").digest();
+    const message = `attest_payment:${escrowPda.toString()}:${fiatAmount.toString()}:${reference.toString("hex")}`;
+    const signature = nacl.sign.detached(decodeUTF8(message), attester.secretKey);
+
+    const tx = await program.methods
+      .attestedRelease(bn(id), fiatAmount, Array.from(reference), Array.from(signature))
+      .accounts({
+        buyer: randomBuyer.publicKey,
+        seller: wallet.publicKey,
+        paymentAttester: paymentAttesterPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    console.log("`attested_release` tx signature:", tx);
+
+    try {
+      await getEscrowAccount(program, id);
+      expect.fail("Escrow account should be closed after the attested release");
+    } catch (err) {
+      expect(err.message).to.include("Account does not exist");
+    }
+  });
+
+  it("user stats updated by the settlements!", async () => {
+    const buyerStats = await getUserStatsAccount(program, randomBuyer.publicKey);
+    const sellerStats = await getUserStatsAccount(program, wallet.publicKey);
+
+    console.log("Buyer stats:", buyerStats);
+
+    // releases, disputes, cancels and abandons of the previous tests
+    expect(buyerStats.tradesAsBuyer).to.greaterThan(0);
+    expect(buyerStats.disputesOpened).to.greaterThan(0);
+    expect(buyerStats.cancellations).to.greaterThan(0);
+    expect(buyerStats.abandons).to.greaterThan(0);
+    // the escrows the buyer never acted on count for the seller only
+    expect(sellerStats.tradesAsSeller).to.greaterThanOrEqual(buyerStats.tradesAsBuyer);
+    expect(sellerStats.disputesWon + sellerStats.disputesLost).to.equal(
+      buyerStats.disputesWon + buyerStats.disputesLost
+    );
+
+    const volume = sellerStats.volumes.find((volume) => volume.mint === randomMint.toString());
+    expect(volume.amount).to.greaterThan(0);
+
+    // canonical bump, set from the account bumps on creation
+    for (const [user, stats] of [
+      [randomBuyer.publicKey, buyerStats],
+      [wallet.publicKey, sellerStats],
+    ] as const) {
+      const [, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [USER_STATS_SEED, user.toBuffer()],
+        program.programId
+      );
+      expect(stats.user).to.equal(user.toString());
+      expect(stats.bump).to.equal(bump);
+    }
+  });
+
   it("`withdraw_spl`!", async () => {
     const tx = await program.methods
       .withdrawSpl()
@@ -1,7 +1,22 @@
 import * as anchor from "@coral-xyz/anchor";
 
-import { DISPUTE_VAULT_SEED, ESCROW_SEED, GLOBAL_CONFIG_SEED, MINT_VAULT_SEED } from "./constants";
-import { escrowParser, globalConfigParser, mintVaultParser } from "./parsers";
+import {
+  DISPUTE_SEED,
+  DISPUTE_VAULT_SEED,
+  ESCROW_SEED,
+  GLOBAL_CONFIG_SEED,
+  MERCHANT_PROFILE_SEED,
+  MINT_VAULT_SEED,
+  USER_STATS_SEED,
+} from "./constants";
+import {
+  disputeParser,
+  escrowParser,
+  globalConfigParser,
+  merchantProfileParser,
+  mintVaultParser,
+  userStatsParser,
+} from "./parsers";
 
 import { P2p } from "../../target/types/p2p";
 import { bn } from "./functions";
@@ -51,10 +66,46 @@ async function getDisputeVaultAccount(
   return await connection.getAccountInfo(disputeVaultPda);
 }
 
+// dispute accounts
+async function getDisputeAccount(program: anchor.Program<P2p>, escrowId: number) {
+  const [disputePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [DISPUTE_SEED, bn(escrowId).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
+  return disputeParser(await program.account.dispute.fetch(disputePda));
+}
+
+// merchant profile accounts
+async function getMerchantProfileAccount(
+  program: anchor.Program<P2p>,
+  merchant: anchor.web3.PublicKey
+) {
+  const [merchantProfilePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [MERCHANT_PROFILE_SEED, merchant.toBuffer()],
+    program.programId
+  );
+
+  return merchantProfileParser(await program.account.merchantProfile.fetch(merchantProfilePda));
+}
+
+// user stats accounts
+async function getUserStatsAccount(program: anchor.Program<P2p>, user: anchor.web3.PublicKey) {
+  const [userStatsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [USER_STATS_SEED, user.toBuffer()],
+    program.programId
+  );
+
+  return userStatsParser(await program.account.userStats.fetch(userStatsPda));
+}
+
 export {
   getGlobalConfigAccount,
   getEscrowAccount,
   getAllEscrowAccounts,
   getMintVaultAccount,
   getDisputeVaultAccount,
+  getDisputeAccount,
+  getMerchantProfileAccount,
+  getUserStatsAccount,
 };
@@ -6,6 +6,12 @@ const GLOBAL_CONFIG_SEED = Buffer.from("global_config");
 const ESCROW_SEED = Buffer.from("escrow");
 const MINT_VAULT_SEED = Buffer.from("mint_vault");
 const DISPUTE_VAULT_SEED = Buffer.from("dispute_vault");
+const DISPUTE_SEED = Buffer.from("dispute");
+const DISPUTE_EVIDENCE_SEED = Buffer.from("dispute_evidence");
+const PAYMENT_ATTESTER_SEED = Buffer.from("payment_attester");
+const ARBITER_REGISTRY_SEED = Buffer.from("arbiter_registry");
+const MERCHANT_PROFILE_SEED = Buffer.from("merchant_profile");
+const USER_STATS_SEED = Buffer.from("user_stats");
 
 // initial configs
 const FEE_BPS = 100; // 1% fee
@@ -14,7 +20,7 @@ const FEE_BPS = 100; // 1% fee
 const DISPUTE_FEE_ESCROW = bn(0.1 * LAMPORTS_PER_SOL); // lamports
 
 // TEST initial configs
-const FIAT_DEADLINE_SECS = bn(1); // TEST
+const FIAT_DEADLINE_SECS = bn(2); // TEST, the escrows are marked as paid right after creation
 const DISPUTE_DEADLINE_SECS = bn(1); // TEST
 
 export {
@@ -26,4 +32,10 @@ export {
   ESCROW_SEED,
   MINT_VAULT_SEED,
   DISPUTE_VAULT_SEED,
+  DISPUTE_SEED,
+  DISPUTE_EVIDENCE_SEED,
+  PAYMENT_ATTESTER_SEED,
+  ARBITER_REGISTRY_SEED,
+  MERCHANT_PROFILE_SEED,
+  USER_STATS_SEED,
 };
@@ -1,7 +1,89 @@
-import { BN } from "@coral-xyz/anchor";
+import { BN, web3 } from "@coral-xyz/anchor";
+import { p256 } from "@noble/curves/p256";
+import { secp256k1 } from "@noble/curves/secp256k1";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { createHash } from "crypto";
 
 function bn(n: number) {
   return new BN(n);
 }
 
-export { bn };
+// last 20 bytes of keccak256(uncompressed pubkey without the 0x04 prefix)
+function ethereumAddress(privateKey: Uint8Array) {
+  const pubkey = secp256k1.getPublicKey(privateKey, false);
+  return Array.from(keccak_256(pubkey.slice(1)).slice(12));
+}
+
+// EIP-191 (personal_sign), v = 27 or 28 as returned by wallets
+function ethereumSign(message: Uint8Array, privateKey: Uint8Array) {
+  const prefix = new TextEncoder().encode(
+    `\x19Ethereum Signed Message:\n${message.length}`
+  );
+  const hash = keccak_256(new Uint8Array([...prefix, ...message]));
+  const signature = secp256k1.sign(hash, privateKey);
+
+  return {
+    signature: Array.from(signature.toCompactRawBytes()),
+    recoveryId: signature.recovery + 27,
+  };
+}
+
+const SECP256R1_PROGRAM_ID = new web3.PublicKey(
+  "Secp256r1SigVerify1111111111111111111111111"
+);
+
+function sha256(data: Uint8Array | string) {
+  return createHash("sha256").update(data).digest();
+}
+
+// WebAuthn assertion over the message, and the secp256r1 precompile instruction verifying it
+function passkeyAssertion(
+  message: Uint8Array,
+  privateKey: Uint8Array,
+  { rpId, origin, clientDataJson }: { rpId: string; origin: string; clientDataJson?: string }
+) {
+  // rp id hash, flags (user present and verified), sign count
+  const authenticatorData = Buffer.concat([sha256(rpId), Buffer.from([0x05, 0, 0, 0, 1])]);
+  const clientData = Buffer.from(
+    clientDataJson ??
+      JSON.stringify({
+        type: "webauthn.get",
+        challenge: sha256(message).toString("base64url"),
+        origin,
+        crossOrigin: false,
+      })
+  );
+
+  const signedData = Buffer.concat([authenticatorData, sha256(clientData)]);
+  const signature = p256.sign(sha256(signedData), privateKey, { lowS: true }).toCompactRawBytes();
+  const pubkey = p256.getPublicKey(privateKey, true);
+
+  // [num_signatures, padding, offsets (7 u16)] then signature, pubkey and signed data
+  const dataStart = 16;
+  const offsets = Buffer.alloc(dataStart);
+  offsets.writeUInt8(1, 0);
+  [
+    dataStart, // signature offset
+    0xffff,
+    dataStart + signature.length, // pubkey offset
+    0xffff,
+    dataStart + signature.length + pubkey.length, // message offset
+    signedData.length,
+    0xffff,
+  ].forEach((value, i) => offsets.writeUInt16LE(value, 2 + i * 2));
+
+  const verifyInstruction = new web3.TransactionInstruction({
+    programId: SECP256R1_PROGRAM_ID,
+    keys: [],
+    data: Buffer.concat([offsets, signature, pubkey, signedData]),
+  });
+
+  return {
+    approval: {
+      passkey: { authenticatorData, clientDataJson: clientData },
+    },
+    verifyInstruction,
+  };
+}
+
+export { bn, ethereumAddress, ethereumSign, passkeyAssertion, sha256 };
@@ -1,13 +1,25 @@
 import * as anchor from "@coral-xyz/anchor";
 
+interface DisputeWindow {
+  enabled: boolean;
+  earliestSecs: anchor.BN;
+  latestSecs: anchor.BN;
+}
+
 interface GlobalConfigParserParams {
   authority: anchor.web3.PublicKey;
   escrowCount: anchor.BN;
   feeBps: number;
   fiatDeadlineSecs: anchor.BN;
-  disputeDeadlineSecs: anchor.BN;
+  disputePolicy: {
+    open: DisputeWindow;
+    fiatPaid: DisputeWindow;
+    reDispute: DisputeWindow;
+  };
   disputeFeeEscrow: anchor.BN;
   availableLamports: anchor.BN;
+  merchantMinBond: anchor.BN;
+  merchantFeeBps: number;
   bump: number;
 }
 
@@ -17,9 +29,12 @@ function globalConfigParser(params: GlobalConfigParserParams) {
     escrowCount: params.escrowCount.toNumber(),
     feeBps: params.feeBps,
     fiatDeadlineSecs: params.fiatDeadlineSecs.toNumber(),
-    disputeDeadlineSecs: params.disputeDeadlineSecs.toNumber(),
+    disputeDeadlineSecs: params.disputePolicy.fiatPaid.earliestSecs.toNumber(),
+    openDisputesEnabled: params.disputePolicy.open.enabled,
     disputeFeeEscrow: params.disputeFeeEscrow.toNumber(),
     availableLamports: params.availableLamports.toNumber(),
+    merchantMinBond: params.merchantMinBond.toNumber(),
+    merchantFeeBps: params.merchantFeeBps,
     bump: params.bump,
   };
 }
@@ -30,8 +45,17 @@ interface EscrowParserParams {
   buyer: anchor.web3.PublicKey;
   mint: anchor.web3.PublicKey;
   amount: anchor.BN;
+  feeBps: number;
+  verifiedMerchant: boolean;
   state: { [kind: string]: { "0": anchor.BN } };
+  fiatDeadline: anchor.BN;
+  paymentProof: { reference: number[]; receiptHash: number[] } | null;
+  buyerActed: boolean;
+  buyerBond: { amount: anchor.BN; inLamports: boolean; cutBps: number } | null;
   disputedBy: { [kind: string]: {} };
+  releaseApprover: { [kind: string]: {} };
+  signerSet: { threshold: number; signers: anchor.web3.PublicKey[] } | null;
+  freeze: { arbiter: anchor.web3.PublicKey; priorState: { [kind: string]: {} } } | null;
   bump: number;
 }
 
@@ -42,9 +66,26 @@ function escrowParser(params: EscrowParserParams) {
     buyer: params.buyer.toString(),
     mint: params.mint.toString(),
     amount: params.amount.toNumber(),
+    feeBps: params.feeBps,
+    verifiedMerchant: params.verifiedMerchant,
     state: Object.keys(params.state)[0],
     timestamp: Object.values(Object.values(params.state)[0])[0].toNumber(),
+    fiatDeadline: params.fiatDeadline.toNumber(),
+    paymentReference: params.paymentProof
+      ? Buffer.from(params.paymentProof.reference).toString("hex")
+      : null,
+    receiptHash: params.paymentProof
+      ? Buffer.from(params.paymentProof.receiptHash).toString("hex")
+      : null,
+    buyerActed: params.buyerActed,
+    buyerBond: params.buyerBond?.amount.toNumber() ?? null,
+    buyerBondInLamports: params.buyerBond?.inLamports ?? null,
     disputedBy: Object.keys(params.disputedBy)[0],
+    releaseApprover: Object.keys(params.releaseApprover)[0],
+    signerSetThreshold: params.signerSet?.threshold ?? null,
+    signerSetSigners: params.signerSet?.signers.map((signer) => signer.toString()) ?? null,
+    frozenBy: params.freeze?.arbiter.toString() ?? null,
+    priorState: params.freeze ? Object.keys(params.freeze.priorState)[0] : null,
     bump: params.bump,
   };
 }
@@ -52,6 +93,12 @@ function escrowParser(params: EscrowParserParams) {
 interface MintVaultParserParams {
   mint: anchor.web3.PublicKey;
   availableAmount: anchor.BN;
+  buyerBond: anchor.BN;
+  buyerBondInLamports: boolean;
+  buyerBondCutBps: number;
+  escrowLimit: anchor.BN;
+  merchantEscrowLimit: anchor.BN;
+  arbiterRewards: { arbiter: anchor.web3.PublicKey; amount: anchor.BN }[];
   isInitialized: boolean;
   bump: number;
 }
@@ -60,9 +107,161 @@ function mintVaultParser(params: MintVaultParserParams) {
   return {
     mint: params.mint.toString(),
     availableAmount: params.availableAmount.toNumber(),
+    buyerBond: params.buyerBond.toNumber(),
+    buyerBondInLamports: params.buyerBondInLamports,
+    buyerBondCutBps: params.buyerBondCutBps,
+    escrowLimit: params.escrowLimit.toNumber(),
+    merchantEscrowLimit: params.merchantEscrowLimit.toNumber(),
+    arbiterRewards: params.arbiterRewards.map((reward) => ({
+      arbiter: reward.arbiter.toString(),
+      amount: reward.amount.toNumber(),
+    })),
     isInitialized: params.isInitialized,
     bump: params.bump,
   };
 }
 
-export { globalConfigParser, escrowParser, mintVaultParser };
+interface DisputeParserParams {
+  escrowId: anchor.BN;
+  escrow: anchor.web3.PublicKey;
+  buyer: anchor.web3.PublicKey;
+  seller: anchor.web3.PublicKey;
+  escrowAmount: anchor.BN;
+  verifiedMerchant: boolean;
+  openedBy: anchor.web3.PublicKey;
+  openedAt: anchor.BN;
+  redisputedAt: anchor.BN | null;
+  buyerDeposit: anchor.BN;
+  sellerDeposit: anchor.BN;
+  depositMint: anchor.web3.PublicKey | null;
+  assignedArbiter: anchor.web3.PublicKey | null;
+  drawSlot: anchor.BN;
+  arbiter: anchor.web3.PublicKey | null;
+  outcome: {
+    resolved?: {
+      buyerBps: number;
+      buyerDepositRefunded: anchor.BN;
+      sellerDepositRefunded: anchor.BN;
+    };
+  };
+  panel: boolean;
+  panelSize: number;
+  panelQuorum: number;
+  panelMembers: anchor.web3.PublicKey[];
+  votingDeadline: anchor.BN;
+  resolutionDeadline: anchor.BN;
+  votes: { arbiter: anchor.web3.PublicKey; ruling: {} }[];
+  pendingRuling: {} | null;
+  appealDeadline: anchor.BN;
+  appellant: anchor.web3.PublicKey | null;
+  appealedArbiter: anchor.web3.PublicKey | null;
+  appealBond: anchor.BN;
+  merchantSlashed: anchor.BN;
+  forceSettled: boolean;
+  slashedArbiters: anchor.web3.PublicKey[];
+  bump: number;
+}
+
+function disputeParser(params: DisputeParserParams) {
+  return {
+    escrowId: params.escrowId.toNumber(),
+    escrow: params.escrow.toString(),
+    buyer: params.buyer.toString(),
+    seller: params.seller.toString(),
+    escrowAmount: params.escrowAmount.toNumber(),
+    verifiedMerchant: params.verifiedMerchant,
+    openedBy: params.openedBy.toString(),
+    openedAt: params.openedAt.toNumber(),
+    redisputedAt: params.redisputedAt?.toNumber() ?? null,
+    buyerDeposit: params.buyerDeposit.toNumber(),
+    sellerDeposit: params.sellerDeposit.toNumber(),
+    depositMint: params.depositMint?.toString() ?? null,
+    assignedArbiter: params.assignedArbiter?.toString() ?? null,
+    drawSlot: params.drawSlot.toNumber(),
+    arbiter: params.arbiter?.toString() ?? null,
+    outcome: Object.keys(params.outcome)[0],
+    buyerBps: params.outcome.resolved?.buyerBps ?? null,
+    buyerDepositRefunded: params.outcome.resolved?.buyerDepositRefunded.toNumber() ?? null,
+    sellerDepositRefunded: params.outcome.resolved?.sellerDepositRefunded.toNumber() ?? null,
+    panel: params.panel,
+    panelSize: params.panelSize,
+    panelQuorum: params.panelQuorum,
+    panelMembers: params.panelMembers.map((member) => member.toString()),
+    votingDeadline: params.votingDeadline.toNumber(),
+    resolutionDeadline: params.resolutionDeadline.toNumber(),
+    votes: params.votes,
+    pendingRuling: params.pendingRuling,
+    appealDeadline: params.appealDeadline.toNumber(),
+    appellant: params.appellant?.toString() ?? null,
+    appealedArbiter: params.appealedArbiter?.toString() ?? null,
+    appealBond: params.appealBond.toNumber(),
+    merchantSlashed: params.merchantSlashed.toNumber(),
+    forceSettled: params.forceSettled,
+    slashedArbiters: params.slashedArbiters.map((arbiter) => arbiter.toString()),
+    bump: params.bump,
+  };
+}
+
+interface MerchantProfileParserParams {
+  merchant: anchor.web3.PublicKey;
+  bond: anchor.BN;
+  unbondingUntil: anchor.BN;
+  slashed: anchor.BN;
+  bondedSince: anchor.BN;
+  pendingDisputes: number;
+  bump: number;
+}
+
+function merchantProfileParser(params: MerchantProfileParserParams) {
+  return {
+    merchant: params.merchant.toString(),
+    bond: params.bond.toNumber(),
+    unbondingUntil: params.unbondingUntil.toNumber(),
+    slashed: params.slashed.toNumber(),
+    bondedSince: params.bondedSince.toNumber(),
+    pendingDisputes: params.pendingDisputes,
+    bump: params.bump,
+  };
+}
+
+interface UserStatsParserParams {
+  user: anchor.web3.PublicKey;
+  tradesAsBuyer: number;
+  tradesAsSeller: number;
+  volumes: { mint: anchor.web3.PublicKey; amount: anchor.BN }[];
+  untrackedTrades: number;
+  disputesOpened: number;
+  disputesWon: number;
+  disputesLost: number;
+  cancellations: number;
+  abandons: number;
+  bump: number;
+}
+
+function userStatsParser(params: UserStatsParserParams) {
+  return {
+    user: params.user.toString(),
+    tradesAsBuyer: params.tradesAsBuyer,
+    tradesAsSeller: params.tradesAsSeller,
+    volumes: params.volumes.map((volume) => ({
+      mint: volume.mint.toString(),
+      amount: volume.amount.toNumber(),
+    })),
+    untrackedTrades: params.untrackedTrades,
+    disputesOpened: params.disputesOpened,
+    disputesWon: params.disputesWon,
+    disputesLost: params.disputesLost,
+    cancellations: params.cancellations,
+    abandons: params.abandons,
+    bump: params.bump,
+  };
+}
+
+export {
+  globalConfigParser,
+  escrowParser,
+  mintVaultParser,
+  disputeParser,
+  merchantProfileParser,
+  userStatsParser,
+};